@@ -0,0 +1,64 @@
+// Compares SpatialHash against checking every pair of objects.
+//
+// Run with `cargo run --release --example spatial_hash_bench`.
+
+use std::time::{Duration, Instant};
+use minui::game::{GameRng, SpatialHash};
+use minui::widgets::WidgetArea;
+
+const OBJECTS: usize = 1000;
+const FIELD_WIDTH: u16 = 400;
+const FIELD_HEIGHT: u16 = 200;
+const ROUNDS: u32 = 50;
+
+fn brute_force(areas: &[WidgetArea]) -> usize {
+    let mut pairs = 0;
+    for (i, a) in areas.iter().enumerate() {
+        for b in &areas[i + 1..] {
+            if a.intersects(b) {
+                pairs += 1;
+            }
+        }
+    }
+    pairs
+}
+
+fn spatial_hash(areas: &[WidgetArea]) -> usize {
+    let mut hash = SpatialHash::new(4);
+    for (id, area) in areas.iter().enumerate() {
+        hash.insert(id, *area);
+    }
+    hash.overlapping_pairs().len()
+}
+
+fn time(rounds: u32, mut f: impl FnMut() -> usize) -> (usize, Duration) {
+    let start = Instant::now();
+    let mut pairs = 0;
+    for _ in 0..rounds {
+        pairs = f();
+    }
+    (pairs, start.elapsed() / rounds)
+}
+
+fn main() {
+    let mut rng = GameRng::from_seed(941);
+    let areas: Vec<WidgetArea> = (0..OBJECTS)
+        .map(|_| {
+            WidgetArea::new(
+                rng.gen_range(0..FIELD_WIDTH),
+                rng.gen_range(0..FIELD_HEIGHT),
+                rng.gen_range(1..4),
+                rng.gen_range(1..4),
+            )
+        })
+        .collect();
+
+    let (brute_pairs, brute_time) = time(ROUNDS, || brute_force(&areas));
+    let (hash_pairs, hash_time) = time(ROUNDS, || spatial_hash(&areas));
+    assert_eq!(brute_pairs, hash_pairs, "both passes should find the same pairs");
+
+    println!("{OBJECTS} objects, {brute_pairs} overlapping pairs, averaged over {ROUNDS} rounds");
+    println!("  brute force:  {brute_time:?}");
+    println!("  spatial hash: {hash_time:?} (including building the grid)");
+    println!("  speedup:      {:.1}x", brute_time.as_secs_f64() / hash_time.as_secs_f64());
+}
@@ -91,10 +91,8 @@ fn main() -> Result<()> {
 
     // Wait for 'q' to be pressed before exiting
     loop {
-        if let Ok(event) = window.get_input() {
-            if let Event::Character('q') = event {
-                break;
-            }
+        if let Ok(Event::Character('q')) = window.get_input() {
+            break;
         }
     }
 
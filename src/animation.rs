@@ -0,0 +1,238 @@
+/* Easing functions and tweens for animating UI and game values.
+    Tweens are driven by elapsed time rather than frame counts, so animations
+    run at the same speed regardless of frame rate.
+ */
+
+use std::time::Duration;
+
+use crate::{Color, ColorPair};
+
+/// Easing curves that map linear progress (0.0..=1.0) to eased progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Quadratic, starting slow
+    EaseIn,
+    /// Quadratic, ending slow
+    EaseOut,
+    /// Quadratic, slow at both ends
+    EaseInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Bounces against the end value before settling, like a dropped ball
+    Bounce,
+}
+
+impl Easing {
+    /// Applies the curve to `t`, which is clamped to 0.0..=1.0
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Bounce => bounce_out(t),
+        }
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Values that can be interpolated between two endpoints.
+pub trait Lerp: Clone {
+    /// Returns the value `t` of the way from `self` to `to`. `t` may be outside
+    /// 0.0..=1.0 for easings that overshoot.
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t as f64
+    }
+}
+
+macro_rules! impl_lerp_int {
+    ($($t:ty),*) => {
+        $(
+            impl Lerp for $t {
+                fn lerp(&self, to: &Self, t: f32) -> Self {
+                    (*self as f64).lerp(&(*to as f64), t).round() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp_int!(u8, u16, u32, i16, i32, i64, usize);
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Color::lerp(*self, *to, t)
+    }
+}
+
+impl Lerp for ColorPair {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        ColorPair::new(self.fg.lerp(to.fg, t), self.bg.lerp(to.bg, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        (self.0.lerp(&to.0, t), self.1.lerp(&to.1, t))
+    }
+}
+
+/// Interpolates from one value to another over a fixed duration.
+#[derive(Debug, Clone)]
+pub struct Tween<T: Lerp> {
+    pub from: T,
+    pub to: T,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns linear progress through the tween, from 0.0 to 1.0
+    pub fn progress(&self, elapsed: Duration) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Returns the value `elapsed` into the tween. Past the end, this is `to`.
+    pub fn value_at(&self, elapsed: Duration) -> T {
+        let t = self.progress(elapsed);
+        if t >= 1.0 {
+            return self.to.clone();
+        }
+        self.from.lerp(&self.to, self.easing.apply(t))
+    }
+
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+}
+
+/// Plays a sequence of tweens back to back.
+#[derive(Debug, Clone)]
+pub struct Timeline<T: Lerp> {
+    tweens: Vec<Tween<T>>,
+    looping: bool,
+}
+
+impl<T: Lerp> Timeline<T> {
+    pub fn new() -> Self {
+        Self {
+            tweens: Vec::new(),
+            looping: false,
+        }
+    }
+
+    /// Appends a tween that starts when the previous one ends.
+    pub fn then(mut self, tween: Tween<T>) -> Self {
+        self.tweens.push(tween);
+        self
+    }
+
+    /// Appends a pause that holds `value` for `duration`.
+    pub fn hold(self, value: T, duration: Duration) -> Self {
+        self.then(Tween::new(value.clone(), value, duration))
+    }
+
+    /// Makes the timeline restart from the beginning when it ends.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// The combined duration of every tween
+    pub fn duration(&self) -> Duration {
+        self.tweens.iter().map(|tween| tween.duration).sum()
+    }
+
+    /// Returns the value `elapsed` into the timeline, or `None` if it's empty.
+    ///
+    /// Non-looping timelines hold their final value once they end.
+    pub fn value_at(&self, elapsed: Duration) -> Option<T> {
+        let total = self.duration();
+        let mut remaining = if self.looping && !total.is_zero() {
+            Duration::from_secs_f64(elapsed.as_secs_f64() % total.as_secs_f64())
+        } else {
+            elapsed
+        };
+
+        for tween in &self.tweens {
+            if remaining < tween.duration {
+                return Some(tween.value_at(remaining));
+            }
+            remaining -= tween.duration;
+        }
+        self.tweens.last().map(|tween| tween.to.clone())
+    }
+
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        !self.looping && elapsed >= self.duration()
+    }
+}
+
+impl<T: Lerp> Default for Timeline<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
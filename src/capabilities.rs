@@ -0,0 +1,95 @@
+//! Detection of what the current terminal supports.
+//!
+//! Detection is based on the environment (`TERM`, `COLORTERM`, `NO_COLOR`)
+//! and whether stdout is a TTY, since terminals can't be asked directly.
+//! Print `TerminalCapabilities::detect().summary()` when reporting rendering
+//! issues.
+
+use std::env;
+use std::io::IsTerminal;
+use crate::color::env_color_override;
+
+/// How many colors the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    /// Colors are disabled or unsupported
+    None,
+    /// The 16 standard ANSI colors
+    Basic,
+    /// The 256-color palette
+    Ansi256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+/// What the crate detected about the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    pub color_support: ColorSupport,
+    /// Whether stdout is a terminal (rather than a pipe or file)
+    pub is_tty: bool,
+    /// The `TERM` environment variable, if set
+    pub term: Option<String>,
+    /// The `COLORTERM` environment variable, if set
+    pub colorterm: Option<String>,
+    /// Whether mouse reporting is likely to work
+    pub mouse: bool,
+}
+
+impl TerminalCapabilities {
+    pub fn detect() -> Self {
+        let is_tty = std::io::stdout().is_terminal();
+        let term = env::var("TERM").ok().filter(|term| !term.is_empty());
+        let colorterm = env::var("COLORTERM").ok().filter(|colorterm| !colorterm.is_empty());
+        let dumb = term.as_deref() == Some("dumb");
+
+        let truecolor = colorterm.as_deref()
+            .is_some_and(|colorterm| colorterm.eq_ignore_ascii_case("truecolor") || colorterm.eq_ignore_ascii_case("24bit"));
+
+        let color_support = if env_color_override(|name| env::var(name).ok()) == Some(false) {
+            ColorSupport::None
+        } else if truecolor {
+            ColorSupport::TrueColor
+        } else if term.as_deref().is_some_and(|term| term.contains("256color")) {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Basic
+        };
+
+        Self {
+            color_support,
+            is_tty,
+            term,
+            colorterm,
+            mouse: is_tty && !dumb,
+        }
+    }
+
+    /// Overrides the detected color support, for when the user knows better
+    /// than the environment (e.g. over SSH, where `COLORTERM` is often dropped).
+    /// Pass the result to `TerminalWindow::set_capabilities` to apply it.
+    pub fn set_color_support(&mut self, support: ColorSupport) {
+        self.color_support = support;
+    }
+
+    pub fn supports_truecolor(&self) -> bool {
+        self.color_support == ColorSupport::TrueColor
+    }
+
+    /// Returns a human-readable, multi-line description of what was detected,
+    /// for logs and bug reports.
+    pub fn summary(&self) -> String {
+        let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "(not set)".to_string());
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+        format!(
+            "color support: {:?}\ntruecolor: {}\ntty: {}\nTERM: {}\nCOLORTERM: {}\nmouse: {}",
+            self.color_support,
+            yes_no(self.supports_truecolor()),
+            yes_no(self.is_tty),
+            describe(&self.term),
+            describe(&self.colorterm),
+            yes_no(self.mouse),
+        )
+    }
+}
@@ -1,4 +1,5 @@
 use crossterm::style::{Color as CrosstermColor};
+use crate::{ColorSupport, TerminalCapabilities};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
@@ -27,6 +28,15 @@ impl Color {
             Color::Transparent => CrosstermColor::Reset,
         }
     }
+
+    /// Interpolates towards `to`.
+    ///
+    /// Colors are named palette entries without RGB values, so there's nothing
+    /// in between to blend through: this returns `self` until `t` reaches 0.5,
+    /// then `to`.
+    pub fn lerp(self, to: Color, t: f32) -> Color {
+        if t < 0.5 { self } else { to }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -39,4 +49,127 @@ impl ColorPair {
     pub const fn new(fg: Color, bg: Color) -> Self {
         Self { fg, bg }
     }
-}
\ No newline at end of file
+
+    /// Returns the pair with foreground and background swapped, for selection
+    /// highlights and cursor cells.
+    ///
+    /// Since `Transparent` means "terminal default", swapping it would leave the
+    /// result looking unchanged. Instead, a transparent background becomes a
+    /// `Black` foreground and a transparent foreground becomes a `White`
+    /// background, matching a typical dark terminal theme.
+    pub const fn inverse(&self) -> Self {
+        let fg = match self.bg {
+            Color::Transparent => Color::Black,
+            color => color,
+        };
+        let bg = match self.fg {
+            Color::Transparent => Color::White,
+            color => color,
+        };
+        Self { fg, bg }
+    }
+}
+
+/// Controls whether colors are emitted when rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Emit colors if the terminal supports them and stdout is a TTY, unless
+    /// the `NO_COLOR` environment variable is set or `TERM` is `dumb`. A
+    /// `CLICOLOR_FORCE` value other than `0` forces colors on.
+    #[default]
+    Auto,
+    /// Always emit colors, regardless of detection
+    Always,
+    /// Never emit colors; text is drawn with the terminal's default colors
+    Never,
+}
+
+impl ColorMode {
+    /// Resolves the mode to whether colors should actually be emitted, using
+    /// freshly detected terminal capabilities.
+    pub fn colors_enabled(self) -> bool {
+        self.colors_enabled_for(&TerminalCapabilities::detect())
+    }
+
+    /// Resolves the mode to whether colors should actually be emitted on a
+    /// terminal with the given capabilities.
+    ///
+    /// `NO_COLOR`, `TERM=dumb` and `CLICOLOR_FORCE` still apply in `Auto`
+    /// mode, even if the capabilities were overridden.
+    pub fn colors_enabled_for(self, capabilities: &TerminalCapabilities) -> bool {
+        self.resolve(capabilities, |name| std::env::var(name).ok())
+    }
+
+    fn resolve(self, capabilities: &TerminalCapabilities, env: impl Fn(&str) -> Option<String>) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => env_color_override(env)
+                .unwrap_or(capabilities.is_tty && capabilities.color_support != ColorSupport::None),
+        }
+    }
+}
+
+/// What the environment says about colors regardless of the terminal:
+/// `Some(true)` if `CLICOLOR_FORCE` forces them on, `Some(false)` if `NO_COLOR`
+/// or `TERM=dumb` turn them off, and `None` otherwise.
+pub(crate) fn env_color_override(env: impl Fn(&str) -> Option<String>) -> Option<bool> {
+    let forced = env("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0");
+    if forced {
+        return Some(true);
+    }
+
+    let no_color = env("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let dumb_term = env("TERM").is_some_and(|term| term == "dumb");
+    (no_color || dumb_term).then_some(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(color_support: ColorSupport, is_tty: bool) -> TerminalCapabilities {
+        TerminalCapabilities {
+            color_support,
+            is_tty,
+            term: None,
+            colorterm: None,
+            mouse: is_tty,
+        }
+    }
+
+    fn resolve(mode: ColorMode, capabilities: &TerminalCapabilities, vars: &[(&str, &str)]) -> bool {
+        mode.resolve(capabilities, |name| {
+            vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn explicit_modes_ignore_detection() {
+        let none = capabilities(ColorSupport::None, false);
+        let full = capabilities(ColorSupport::TrueColor, true);
+        assert!(resolve(ColorMode::Always, &none, &[("NO_COLOR", "1")]));
+        assert!(!resolve(ColorMode::Never, &full, &[("CLICOLOR_FORCE", "1")]));
+    }
+
+    #[test]
+    fn auto_follows_capabilities() {
+        assert!(resolve(ColorMode::Auto, &capabilities(ColorSupport::Basic, true), &[]));
+        assert!(!resolve(ColorMode::Auto, &capabilities(ColorSupport::None, true), &[]));
+        // Redirected output
+        assert!(!resolve(ColorMode::Auto, &capabilities(ColorSupport::TrueColor, false), &[]));
+    }
+
+    #[test]
+    fn auto_respects_the_environment() {
+        let full = capabilities(ColorSupport::TrueColor, true);
+        assert!(!resolve(ColorMode::Auto, &full, &[("NO_COLOR", "1")]));
+        assert!(!resolve(ColorMode::Auto, &full, &[("TERM", "dumb")]));
+        // An empty NO_COLOR doesn't count
+        assert!(resolve(ColorMode::Auto, &full, &[("NO_COLOR", "")]));
+
+        let redirected = capabilities(ColorSupport::None, false);
+        assert!(resolve(ColorMode::Auto, &redirected, &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]));
+        assert!(!resolve(ColorMode::Auto, &redirected, &[("CLICOLOR_FORCE", "0")]));
+    }
+}
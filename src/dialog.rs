@@ -0,0 +1,296 @@
+//! Blocking dialogs for asking the user one thing.
+//!
+//! Each helper draws a centered box over the current screen, runs its own
+//! small event loop until the user answers, then puts back what was under the
+//! box. Mouse clicks are handled if mouse capture has been enabled (see
+//! `MouseHandler::enable_capture`).
+//!
+//! If the terminal is resized while a dialog is open, the window is resized
+//! to match, keeping whatever of the old screen still fits, and the dialog is
+//! re-centered. The caller never sees the `Event::Resize`, so redraw after
+//! the dialog returns if the layout depends on the window size.
+
+use crate::input::MouseHandler;
+use crate::render::buffer::Cell;
+use crate::text::{cell_width, clip_start_to_cells, clip_to_cells};
+use crate::widgets::{center_widget, BorderChars, WidgetArea};
+use crate::{Color, ColorPair, Event, Result, TerminalWindow, Window};
+
+const YES: &str = "[ Yes ]";
+const NO: &str = "[ No ]";
+const BUTTON_GAP: u16 = 2;
+/// How long each poll waits for input, so waiting for an answer doesn't spin
+const POLL_RATE_MS: u64 = 50;
+
+/// Asks a yes/no question and returns the answer.
+///
+/// `y`/`n` answer directly; Left/Right/Tab move between the buttons and Enter
+/// picks the highlighted one (Yes by default). Esc answers no.
+pub fn confirm(window: &mut TerminalWindow, message: &str) -> Result<bool> {
+    let lines: Vec<&str> = message.lines().collect();
+    let mut layout = ConfirmLayout::new(window, &lines);
+    let mut saved = save_area(window, layout.area);
+    let mut input = MouseHandler::new();
+    input.set_poll_rate(POLL_RATE_MS);
+    let mut yes_selected = true;
+    let mut redraw = true;
+
+    let answer = loop {
+        if redraw {
+            draw_confirm(window, &layout, &lines, yes_selected)?;
+            redraw = false;
+        }
+
+        let mut answer = None;
+        for event in input.poll()? {
+            match event {
+                Event::Character('y' | 'Y') => answer = Some(true),
+                Event::Character('n' | 'N') | Event::Escape => answer = Some(false),
+                Event::Enter => answer = Some(yes_selected),
+                Event::KeyLeft | Event::KeyRight | Event::Tab | Event::BackTab => {
+                    yes_selected = !yes_selected;
+                    redraw = true;
+                }
+                Event::MouseClick { x, y, .. } if layout.yes.contains_point(x, y) => answer = Some(true),
+                Event::MouseClick { x, y, .. } if layout.no.contains_point(x, y) => answer = Some(false),
+                Event::Resize { width, height } => {
+                    resize_under_dialog(window, &saved, width, height)?;
+                    layout = ConfirmLayout::new(window, &lines);
+                    saved = save_area(window, layout.area);
+                    redraw = true;
+                }
+                _ => {}
+            }
+            if answer.is_some() {
+                break;
+            }
+        }
+        if let Some(answer) = answer {
+            break answer;
+        }
+    };
+
+    restore_area(window, &saved)?;
+    Ok(answer)
+}
+
+/// Where the confirm dialog and its buttons go in the window
+struct ConfirmLayout {
+    area: WidgetArea,
+    yes: WidgetArea,
+    no: WidgetArea,
+}
+
+impl ConfirmLayout {
+    fn new(window: &TerminalWindow, lines: &[&str]) -> Self {
+        let buttons_width = cell_width(YES) + BUTTON_GAP + cell_width(NO);
+        let content_width = lines.iter().map(|line| cell_width(line)).max().unwrap_or(0).max(buttons_width);
+        let area = dialog_area(window, content_width, lines.len() as u16 + 2);
+
+        // Buttons on the last row, centered
+        let inner = area.inset_xy(2, 1);
+        let buttons_y = inner.bottom().saturating_sub(1);
+        let yes_x = inner.x + inner.width.saturating_sub(buttons_width) / 2;
+        let no_x = yes_x + cell_width(YES) + BUTTON_GAP;
+        Self {
+            area,
+            yes: WidgetArea::new(yes_x, buttons_y, cell_width(YES), 1),
+            no: WidgetArea::new(no_x, buttons_y, cell_width(NO), 1),
+        }
+    }
+}
+
+/// Draws the confirm dialog with the given selection
+fn draw_confirm(window: &mut TerminalWindow, layout: &ConfirmLayout, lines: &[&str], yes_selected: bool) -> Result<()> {
+    draw_frame(window, layout.area)?;
+    let inner = layout.area.inset_xy(2, 1);
+    for (i, line) in lines.iter().enumerate().take(inner.height.saturating_sub(2) as usize) {
+        window.write_str(inner.y + i as u16, inner.x, clip_to_cells(line, inner.width))?;
+    }
+
+    let selected = ColorPair::new(Color::Black, Color::White);
+    let (yes_colors, no_colors) = if yes_selected { (Some(selected), None) } else { (None, Some(selected)) };
+    window.write_str_maybe_colored(layout.yes.y, layout.yes.x, YES, yes_colors)?;
+    window.write_str_maybe_colored(layout.no.y, layout.no.x, NO, no_colors)?;
+    window.flush()
+}
+
+/// Asks for a line of text. Returns `None` if the user cancels with Esc.
+pub fn prompt(window: &mut TerminalWindow, message: &str) -> Result<Option<String>> {
+    let prompt_area = |window: &TerminalWindow| {
+        let (window_width, _) = window.get_size();
+        let content_width = (cell_width(message) + 1).max(40).min(window_width.saturating_sub(4));
+        dialog_area(window, content_width, 2)
+    };
+    let mut area = prompt_area(window);
+    let mut saved = save_area(window, area);
+    let mut input = MouseHandler::new();
+    input.set_poll_rate(POLL_RATE_MS);
+    let mut text = String::new();
+    let mut redraw = true;
+
+    let answer = loop {
+        if redraw {
+            draw_frame(window, area)?;
+            let inner = area.inset_xy(2, 1);
+            window.write_str(inner.y, inner.x, clip_to_cells(message, inner.width))?;
+
+            // The input line scrolls to keep the end (and cursor) visible
+            let field = format!("{}_", text);
+            let visible = clip_start_to_cells(&field, inner.width);
+            let padding = " ".repeat(inner.width.saturating_sub(cell_width(visible)) as usize);
+            let field_colors = ColorPair::new(Color::White, Color::Blue);
+            window.write_str_colored(inner.y + 1, inner.x, &format!("{}{}", visible, padding), field_colors)?;
+            window.flush()?;
+            redraw = false;
+        }
+
+        let mut answer = None;
+        for event in input.poll()? {
+            match event {
+                Event::Character(c) => {
+                    text.push(c);
+                    redraw = true;
+                }
+                Event::Backspace => redraw = text.pop().is_some(),
+                Event::Enter => answer = Some(Some(text.clone())),
+                Event::Escape => answer = Some(None),
+                Event::Resize { width, height } => {
+                    resize_under_dialog(window, &saved, width, height)?;
+                    area = prompt_area(window);
+                    saved = save_area(window, area);
+                    redraw = true;
+                }
+                _ => {}
+            }
+            if answer.is_some() {
+                break;
+            }
+        }
+        if let Some(answer) = answer {
+            break answer;
+        }
+    };
+
+    restore_area(window, &saved)?;
+    Ok(answer)
+}
+
+/// The dialog's box for content of the given size, centered in the window
+fn dialog_area(window: &TerminalWindow, content_width: u16, content_height: u16) -> WidgetArea {
+    let (width, height) = window.get_size();
+    center_widget(WidgetArea::new(0, 0, width, height), (content_width + 4, content_height + 2))
+}
+
+fn draw_frame(window: &mut TerminalWindow, area: WidgetArea) -> Result<()> {
+    if area.width < 2 || area.height < 2 {
+        return Ok(());
+    }
+
+    let chars = BorderChars::single_line();
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let inner_width = area.width - 2;
+    window.write_str(area.y, area.x, &chars.top_left.to_string())?;
+    window.write_repeated(area.y, area.x + 1, chars.horizontal, inner_width, None)?;
+    window.write_str(area.y, right, &chars.top_right.to_string())?;
+    for y in area.y + 1..bottom {
+        window.write_str(y, area.x, &chars.vertical.to_string())?;
+        window.write_repeated(y, area.x + 1, ' ', inner_width, None)?;
+        window.write_str(y, right, &chars.vertical.to_string())?;
+    }
+    window.write_str(bottom, area.x, &chars.bottom_left.to_string())?;
+    window.write_repeated(bottom, area.x + 1, chars.horizontal, inner_width, None)?;
+    window.write_str(bottom, right, &chars.bottom_right.to_string())
+}
+
+/// The cells under a dialog, including colors and attributes, row by row
+type SavedArea = Vec<(u16, u16, Vec<Cell>)>;
+
+fn save_area(window: &TerminalWindow, area: WidgetArea) -> SavedArea {
+    (area.y..area.bottom())
+        .map(|y| {
+            let cells = (area.x..area.right()).filter_map(|x| window.buffer_cell(x, y)).collect();
+            (y, area.x, cells)
+        })
+        .collect()
+}
+
+/// Puts the saved cells back without flushing, skipping any that no longer
+/// fit in the window
+fn put_back(window: &mut TerminalWindow, saved: &SavedArea) -> Result<()> {
+    let (width, height) = window.get_size();
+    for (y, x, cells) in saved.iter().filter(|(y, _, _)| *y < height) {
+        for (offset, cell) in cells.iter().enumerate() {
+            let cell_x = x + offset as u16;
+            if cell_x < width {
+                window.restore_cell(cell_x, *y, cell)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn restore_area(window: &mut TerminalWindow, saved: &SavedArea) -> Result<()> {
+    put_back(window, saved)?;
+    window.flush()
+}
+
+/// Resizes the window while a dialog is open, keeping what was under the
+/// dialog and whatever else of the screen still fits. The caller then
+/// re-centers the dialog and saves the cells under its new position.
+fn resize_under_dialog(window: &mut TerminalWindow, under_dialog: &SavedArea, width: u16, height: u16) -> Result<()> {
+    put_back(window, under_dialog)?;
+    let (old_width, old_height) = window.get_size();
+    let screen = save_area(window, WidgetArea::new(0, 0, old_width, old_height));
+
+    window.resize(width, height)?;
+    put_back(window, &screen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::{ColorSupport, TerminalCapabilities};
+
+    fn window(width: u16, height: u16) -> TerminalWindow {
+        let capabilities = TerminalCapabilities {
+            color_support: ColorSupport::Basic,
+            is_tty: true,
+            term: None,
+            colorterm: None,
+            mouse: true,
+        };
+        let mut window = TerminalWindow::headless(width, height, capabilities);
+        window.set_auto_flush(false);
+        window
+    }
+
+    fn row(window: &TerminalWindow, y: u16) -> String {
+        let (width, _) = window.get_size();
+        (0..width).filter_map(|x| window.buffer_cell(x, y)).map(|cell| cell.ch).collect()
+    }
+
+    #[test]
+    fn resizing_keeps_the_screen_under_the_dialog() {
+        let mut window = window(40, 12);
+        window.write_str(0, 0, "top left").unwrap();
+        window.write_str(6, 0, "under the dialog").unwrap();
+
+        let lines = ["Quit?"];
+        let layout = ConfirmLayout::new(&window, &lines);
+        let saved = save_area(&window, layout.area);
+        draw_confirm(&mut window, &layout, &lines, true).unwrap();
+        assert!(!row(&window, 6).starts_with("under the dialog"));
+
+        resize_under_dialog(&mut window, &saved, 30, 8).unwrap();
+        assert_eq!(window.get_size(), (30, 8));
+        assert!(row(&window, 0).starts_with("top left"));
+        assert!(row(&window, 6).starts_with("under the dialog"));
+
+        // The dialog is re-centered inside the new size
+        let layout = ConfirmLayout::new(&window, &lines);
+        assert!(layout.area.right() <= 30 && layout.area.bottom() <= 8);
+        assert!(layout.area.contains_point(layout.yes.x, layout.yes.y));
+        assert!(layout.area.contains_point(layout.no.right() - 1, layout.no.y));
+    }
+}
@@ -14,6 +14,10 @@ pub enum Error {
     #[error("Invalid Input: {0}")]
     InputError(String),
 
+    /// Errors reading or writing save files (bad format, version mismatch)
+    #[error("Save error: {0}")]
+    SaveError(String),
+
     /// Underlying I/O errors from the terminal
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
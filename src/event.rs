@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, PartialEq)]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Event {
     // Keyboard events
     Character(char),
@@ -6,15 +8,36 @@ pub enum Event {
     KeyDown,
     KeyLeft,
     KeyRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
     Delete,
     Backspace,
     Enter,
     Escape,
+    Tab,
+    /// Shift+Tab
+    BackTab,
+    /// F1 through F12 (and higher, on keyboards that have them)
     FunctionKey(u8),
+    /// A key pressed together with Ctrl or Alt (or Shift, for keys other than
+    /// characters, whose case already reflects Shift).
+    ///
+    /// Without `KeyboardHandler::enable_key_disambiguation` (or on terminals
+    /// that don't support it) some chords can't be told apart from plain keys:
+    /// Ctrl+M arrives as `Enter`, Ctrl+I as `Tab` and Ctrl+[ as `Escape`.
+    KeyWithModifiers { key: Box<Event>, modifiers: Modifiers },
 
-    // Mouse events (placeholder for future implementation)
+    // Mouse events (see `MouseHandler`)
     MouseMove { x: u16, y: u16 },
+    /// A button was pressed
     MouseClick { x: u16, y: u16, button: MouseButton },
+    /// The mouse moved while a button was held
+    MouseDrag { x: u16, y: u16, button: MouseButton },
+    /// A button was let go
+    MouseRelease { x: u16, y: u16, button: MouseButton },
     MouseScroll { delta: i8 },
 
     // Window events (optional, for future use)
@@ -23,7 +46,113 @@ pub enum Event {
     Unknown,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Modifier keys held during a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl Modifiers {
+    pub const fn ctrl() -> Self {
+        Self { ctrl: true, alt: false, shift: false }
+    }
+
+    pub const fn alt() -> Self {
+        Self { ctrl: false, alt: true, shift: false }
+    }
+
+    pub const fn shift() -> Self {
+        Self { ctrl: false, alt: false, shift: true }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.ctrl && !self.alt && !self.shift
+    }
+}
+
+impl Event {
+    /// Wraps a key event with modifiers. Returns the event unchanged if
+    /// `modifiers` is empty.
+    pub fn with_modifiers(self, modifiers: Modifiers) -> Event {
+        if modifiers.is_empty() {
+            self
+        } else {
+            Event::KeyWithModifiers { key: Box::new(self), modifiers }
+        }
+    }
+
+    /// Maps a crossterm key event to an `Event`.
+    pub(crate) fn from_crossterm_key(key: &KeyEvent) -> Event {
+        let event = match key.code {
+            KeyCode::Char(c) => Event::Character(c),
+            KeyCode::Up => Event::KeyUp,
+            KeyCode::Down => Event::KeyDown,
+            KeyCode::Left => Event::KeyLeft,
+            KeyCode::Right => Event::KeyRight,
+            KeyCode::Home => Event::Home,
+            KeyCode::End => Event::End,
+            KeyCode::PageUp => Event::PageUp,
+            KeyCode::PageDown => Event::PageDown,
+            KeyCode::Insert => Event::Insert,
+            KeyCode::Delete => Event::Delete,
+            KeyCode::Backspace => Event::Backspace,
+            KeyCode::Enter => Event::Enter,
+            KeyCode::Esc => Event::Escape,
+            KeyCode::Tab => Event::Tab,
+            KeyCode::BackTab => Event::BackTab,
+            KeyCode::F(n) => Event::FunctionKey(n),
+            _ => return Event::Unknown,
+        };
+
+        let modifiers = Modifiers {
+            ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+            alt: key.modifiers.contains(KeyModifiers::ALT),
+            // A character's case already says whether Shift was held, and
+            // BackTab is Shift+Tab
+            shift: key.modifiers.contains(KeyModifiers::SHIFT)
+                && !matches!(event, Event::Character(_) | Event::BackTab),
+        };
+        event.with_modifiers(modifiers)
+    }
+
+    /// Whether this is a keyboard event, including keys pressed with modifiers.
+    pub fn is_key(&self) -> bool {
+        !matches!(self, Event::Resize { .. } | Event::Unknown) && !self.is_mouse()
+    }
+
+    /// Whether this is any mouse event, including scrolling.
+    pub fn is_mouse(&self) -> bool {
+        self.is_pointer() || matches!(self, Event::MouseScroll { .. })
+    }
+
+    /// Whether this is a mouse event with a position (clicks, drags, releases
+    /// and moves).
+    pub fn is_pointer(&self) -> bool {
+        self.as_mouse().is_some()
+    }
+
+    /// Returns the position of pointer events.
+    pub fn mouse_position(&self) -> Option<(u16, u16)> {
+        self.as_mouse().map(|(x, y, _)| (x, y))
+    }
+
+    /// Returns the position and button of mouse events, with no button for moves.
+    ///
+    /// Scroll events don't carry a position, so they return `None`.
+    pub fn as_mouse(&self) -> Option<(u16, u16, Option<MouseButton>)> {
+        match *self {
+            Event::MouseMove { x, y } => Some((x, y, None)),
+            Event::MouseClick { x, y, button }
+            | Event::MouseDrag { x, y, button }
+            | Event::MouseRelease { x, y, button } => Some((x, y, Some(button))),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MouseButton {
     Left,
     Right,
@@ -1 +1,356 @@
-// Collision detection system
\ No newline at end of file
+// Collision detection system
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::widgets::WidgetArea;
+
+/// Returns true if two objects should collide, given each one's `layer` (the
+/// layers it's on) and `mask` (the layers it collides with).
+///
+/// Both sides have to agree: each must be on a layer the other's mask includes.
+pub fn collides(a_layer: u32, a_mask: u32, b_layer: u32, b_mask: u32) -> bool {
+    a_layer & b_mask != 0 && b_layer & a_mask != 0
+}
+
+/// An axis-aligned box registered with a `CollisionWorld`.
+///
+/// Layers are bitmasks, e.g. `const PLAYER: u32 = 1 << 0; const ENEMY: u32 = 1 << 1;`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Body {
+    pub area: WidgetArea,
+    /// The layers this body is on
+    pub layer: u32,
+    /// The layers this body collides with
+    pub mask: u32,
+}
+
+impl Body {
+    /// A body on `layer` that collides with every layer.
+    pub fn new(area: WidgetArea, layer: u32) -> Self {
+        Self {
+            area,
+            layer,
+            mask: u32::MAX,
+        }
+    }
+
+    pub fn with_mask(mut self, mask: u32) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Returns true if the two bodies overlap and their layers/masks allow a collision.
+    pub fn collides_with(&self, other: &Body) -> bool {
+        collides(self.layer, self.mask, other.layer, other.mask) && self.area.intersects(&other.area)
+    }
+}
+
+/// Identifies a body added to a `CollisionWorld`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BodyId(u64);
+
+/// A set of bodies that can be queried for overlaps.
+#[derive(Debug, Clone, Default)]
+pub struct CollisionWorld {
+    bodies: Vec<(BodyId, Body)>,
+    next_id: u64,
+}
+
+impl CollisionWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, body: Body) -> BodyId {
+        let id = BodyId(self.next_id);
+        self.next_id += 1;
+        self.bodies.push((id, body));
+        id
+    }
+
+    pub fn remove(&mut self, id: BodyId) -> Option<Body> {
+        let index = self.bodies.iter().position(|(body_id, _)| *body_id == id)?;
+        Some(self.bodies.remove(index).1)
+    }
+
+    pub fn get(&self, id: BodyId) -> Option<&Body> {
+        self.bodies.iter().find(|(body_id, _)| *body_id == id).map(|(_, body)| body)
+    }
+
+    pub fn get_mut(&mut self, id: BodyId) -> Option<&mut Body> {
+        self.bodies.iter_mut().find(|(body_id, _)| *body_id == id).map(|(_, body)| body)
+    }
+
+    /// Moves a body. Returns false if the id isn't in the world.
+    pub fn set_area(&mut self, id: BodyId, area: WidgetArea) -> bool {
+        match self.get_mut(id) {
+            Some(body) => {
+                body.area = area;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bodies.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (BodyId, &Body)> {
+        self.bodies.iter().map(|(id, body)| (*id, body))
+    }
+
+    /// Returns the bodies on any of the given `layers` that overlap `area`.
+    pub fn query(&self, area: WidgetArea, layers: u32) -> Vec<BodyId> {
+        self.bodies.iter()
+            .filter(|(_, body)| body.layer & layers != 0 && body.area.intersects(&area))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Returns the bodies that collide with `id`, respecting layers and masks.
+    pub fn colliding_with(&self, id: BodyId) -> Vec<BodyId> {
+        let Some(body) = self.get(id) else {
+            return Vec::new();
+        };
+
+        self.bodies.iter()
+            .filter(|(other_id, other)| *other_id != id && body.collides_with(other))
+            .map(|(other_id, _)| *other_id)
+            .collect()
+    }
+
+    /// Returns every pair of colliding bodies, each pair once.
+    pub fn collision_pairs(&self) -> Vec<(BodyId, BodyId)> {
+        let mut pairs = Vec::new();
+        for (i, (a_id, a)) in self.bodies.iter().enumerate() {
+            for (b_id, b) in &self.bodies[i + 1..] {
+                if a.collides_with(b) {
+                    pairs.push((*a_id, *b_id));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+/// A uniform grid that buckets areas by cell, so overlap checks only look at
+/// nearby objects instead of every pair.
+///
+/// Pick a `cell_size` around the size of a typical object; much smaller cells
+/// put large objects in many buckets, much larger ones put too many objects
+/// in each bucket.
+#[derive(Debug, Clone)]
+pub struct SpatialHash<Id> {
+    cell_size: u16,
+    cells: HashMap<(u16, u16), Vec<Id>>,
+    areas: HashMap<Id, WidgetArea>,
+}
+
+impl<Id: Copy + Eq + Hash> SpatialHash<Id> {
+    pub fn new(cell_size: u16) -> Self {
+        Self {
+            cell_size: cell_size.max(1),
+            cells: HashMap::new(),
+            areas: HashMap::new(),
+        }
+    }
+
+    /// The range of grid cells (inclusive) that an area touches. Empty areas
+    /// are treated as a single point at their position.
+    fn cell_range(&self, area: &WidgetArea) -> ((u16, u16), (u16, u16)) {
+        let right = area.right().saturating_sub(1).max(area.x);
+        let bottom = area.bottom().saturating_sub(1).max(area.y);
+        (
+            (area.x / self.cell_size, area.y / self.cell_size),
+            (right / self.cell_size, bottom / self.cell_size),
+        )
+    }
+
+    /// Adds an object, replacing its previous area if it was already present.
+    pub fn insert(&mut self, id: Id, area: WidgetArea) {
+        self.remove(id);
+
+        let ((x1, y1), (x2, y2)) = self.cell_range(&area);
+        for cy in y1..=y2 {
+            for cx in x1..=x2 {
+                self.cells.entry((cx, cy)).or_default().push(id);
+            }
+        }
+        self.areas.insert(id, area);
+    }
+
+    pub fn remove(&mut self, id: Id) -> Option<WidgetArea> {
+        let area = self.areas.remove(&id)?;
+
+        let ((x1, y1), (x2, y2)) = self.cell_range(&area);
+        for cy in y1..=y2 {
+            for cx in x1..=x2 {
+                if let Some(bucket) = self.cells.get_mut(&(cx, cy)) {
+                    bucket.retain(|other| *other != id);
+                    if bucket.is_empty() {
+                        self.cells.remove(&(cx, cy));
+                    }
+                }
+            }
+        }
+        Some(area)
+    }
+
+    pub fn area(&self, id: Id) -> Option<WidgetArea> {
+        self.areas.get(&id).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.areas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.areas.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.areas.clear();
+    }
+
+    /// Returns the objects whose areas overlap `area`, each once.
+    pub fn query(&self, area: WidgetArea) -> Vec<Id> {
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+
+        let ((x1, y1), (x2, y2)) = self.cell_range(&area);
+        for cy in y1..=y2 {
+            for cx in x1..=x2 {
+                for &id in self.cells.get(&(cx, cy)).into_iter().flatten() {
+                    if seen.insert(id) && self.areas[&id].intersects(&area) {
+                        found.push(id);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns every pair of objects whose areas overlap, each pair once.
+    pub fn overlapping_pairs(&self) -> Vec<(Id, Id)> {
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::new();
+
+        for bucket in self.cells.values() {
+            for (i, &a) in bucket.iter().enumerate() {
+                for &b in &bucket[i + 1..] {
+                    let already_seen = !seen.insert((a, b)) || seen.contains(&(b, a));
+                    if !already_seen && self.areas[&a].intersects(&self.areas[&b]) {
+                        pairs.push((a, b));
+                    }
+                }
+            }
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: u32 = 1 << 0;
+    const ENEMY: u32 = 1 << 1;
+    const BULLET: u32 = 1 << 2;
+
+    #[test]
+    fn layers_and_masks_must_agree_both_ways() {
+        // (a_layer, a_mask, b_layer, b_mask, expected)
+        let cases = [
+            (PLAYER, ENEMY, ENEMY, PLAYER, true),
+            // B is on a layer A collides with, but B ignores A's layer
+            (PLAYER, ENEMY, ENEMY, BULLET, false),
+            (PLAYER, ENEMY, ENEMY, 0, false),
+            (PLAYER, 0, ENEMY, PLAYER, false),
+            // Neither is on a layer the other collides with
+            (PLAYER, BULLET, ENEMY, BULLET, false),
+            (PLAYER | BULLET, ENEMY, ENEMY, BULLET, true),
+            (PLAYER, u32::MAX, ENEMY, u32::MAX, true),
+            (0, u32::MAX, ENEMY, u32::MAX, false),
+        ];
+        for (a_layer, a_mask, b_layer, b_mask, expected) in cases {
+            assert_eq!(collides(a_layer, a_mask, b_layer, b_mask), expected, "{a_layer:b}/{a_mask:b} vs {b_layer:b}/{b_mask:b}");
+            assert_eq!(collides(b_layer, b_mask, a_layer, a_mask), expected, "reversed");
+        }
+    }
+
+    #[test]
+    fn bodies_collide_only_when_overlapping_and_allowed() {
+        let player = Body::new(WidgetArea::new(0, 0, 2, 2), PLAYER).with_mask(ENEMY);
+        let enemy = Body::new(WidgetArea::new(1, 1, 2, 2), ENEMY);
+        let friendly_bullet = Body::new(WidgetArea::new(1, 1, 1, 1), BULLET).with_mask(ENEMY);
+        let far_enemy = Body::new(WidgetArea::new(5, 5, 2, 2), ENEMY);
+
+        assert!(player.collides_with(&enemy) && enemy.collides_with(&player));
+        assert!(!player.collides_with(&friendly_bullet));
+        assert!(!player.collides_with(&far_enemy));
+    }
+
+    /// The same pairs `SpatialHash` should find, by checking every pair
+    fn brute_force_pairs(areas: &[WidgetArea]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, a) in areas.iter().enumerate() {
+            for (j, b) in areas.iter().enumerate().skip(i + 1) {
+                if a.intersects(b) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn spatial_hash_matches_brute_force() {
+        let mut rng = crate::game::GameRng::from_seed(941);
+        let areas: Vec<WidgetArea> = (0..300)
+            .map(|_| WidgetArea::new(rng.gen_range(0..60), rng.gen_range(0..30), rng.gen_range(1..9), rng.gen_range(1..5)))
+            .collect();
+
+        let mut hash = SpatialHash::new(4);
+        for (id, area) in areas.iter().enumerate() {
+            hash.insert(id, *area);
+        }
+
+        let mut pairs: Vec<_> = hash.overlapping_pairs()
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        let found = pairs.len();
+        pairs.sort();
+        pairs.dedup();
+        assert_eq!(pairs.len(), found, "duplicate pairs");
+        assert_eq!(pairs, brute_force_pairs(&areas));
+    }
+
+    #[test]
+    fn bodies_spanning_several_cells_pair_once() {
+        let mut hash = SpatialHash::new(2);
+        // Both cover the same four cells
+        hash.insert('a', WidgetArea::new(0, 0, 4, 4));
+        hash.insert('b', WidgetArea::new(1, 1, 3, 3));
+        hash.insert('c', WidgetArea::new(10, 10, 1, 1));
+
+        let pairs = hash.overlapping_pairs();
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0] == ('a', 'b') || pairs[0] == ('b', 'a'));
+
+        let mut found = hash.query(WidgetArea::new(0, 0, 20, 20));
+        found.sort();
+        assert_eq!(found, vec!['a', 'b', 'c']);
+    }
+}
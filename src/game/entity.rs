@@ -0,0 +1,227 @@
+/* A lightweight entity store for games that outgrow a single player struct.
+    This isn't a full ECS: entities are generational ids, and components live
+    in `ComponentMap`s indexed by entity. Position, velocity and sprite maps
+    are built in, and games can keep their own maps for anything else.
+ */
+
+use crate::{ColorPair, Result, Window};
+
+/// A handle to an entity. Handles to despawned entities are never reused, so
+/// they stop matching once the entity is gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Movement in cells per second
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity {
+    pub dx: f32,
+    pub dy: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub glyph: char,
+    pub colors: Option<ColorPair>,
+}
+
+/// Components of one type, stored by entity.
+#[derive(Debug, Clone)]
+pub struct ComponentMap<T> {
+    entries: Vec<Option<(u32, T)>>,
+}
+
+impl<T> ComponentMap<T> {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Sets the entity's component, returning the previous one.
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        let index = entity.index as usize;
+        if index >= self.entries.len() {
+            self.entries.resize_with(index + 1, || None);
+        }
+
+        let previous = self.entries[index].replace((entity.generation, component));
+        previous.and_then(|(generation, old)| (generation == entity.generation).then_some(old))
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let slot = self.entries.get_mut(entity.index as usize)?;
+        match slot {
+            Some((generation, _)) if *generation == entity.generation => slot.take().map(|(_, c)| c),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        match self.entries.get(entity.index as usize)? {
+            Some((generation, component)) if *generation == entity.generation => Some(component),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self.entries.get_mut(entity.index as usize)? {
+            Some((generation, component)) if *generation == entity.generation => Some(component),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.entries.iter().enumerate().filter_map(|(index, entry)| {
+            entry.as_ref().map(|(generation, component)| {
+                (Entity { index: index as u32, generation: *generation }, component)
+            })
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.entries.iter_mut().enumerate().filter_map(|(index, entry)| {
+            entry.as_mut().map(|(generation, component)| {
+                (Entity { index: index as u32, generation: *generation }, component)
+            })
+        })
+    }
+
+    /// Iterates over entities that have a component in both this map and `other`.
+    pub fn iter_with<'a, U>(&'a self, other: &'a ComponentMap<U>) -> impl Iterator<Item = (Entity, &'a T, &'a U)> {
+        self.iter().filter_map(move |(entity, a)| other.get(entity).map(|b| (entity, a, b)))
+    }
+
+    /// Like `iter_with`, but with mutable access to this map's components.
+    pub fn iter_mut_with<'a, U>(&'a mut self, other: &'a ComponentMap<U>) -> impl Iterator<Item = (Entity, &'a mut T, &'a U)> {
+        self.iter_mut().filter_map(move |(entity, a)| other.get(entity).map(|b| (entity, a, b)))
+    }
+}
+
+impl<T> Default for ComponentMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates entities and holds the built-in component maps.
+#[derive(Debug, Clone, Default)]
+pub struct EntityStore {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    pub positions: ComponentMap<Position>,
+    pub velocities: ComponentMap<Velocity>,
+    pub sprites: ComponentMap<Sprite>,
+}
+
+impl EntityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self) -> Entity {
+        if let Some(index) = self.free.pop() {
+            self.alive[index as usize] = true;
+            return Entity { index, generation: self.generations[index as usize] };
+        }
+
+        let index = self.generations.len() as u32;
+        self.generations.push(0);
+        self.alive.push(true);
+        Entity { index, generation: 0 }
+    }
+
+    /// Removes an entity and its built-in components. Returns false if it
+    /// was already gone.
+    ///
+    /// Components in your own maps aren't touched; remove them yourself or
+    /// call `retain_alive` on the map.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        self.positions.remove(entity);
+        self.velocities.remove(entity);
+        self.sprites.remove(entity);
+
+        let index = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        self.alive.get(index) == Some(&true) && self.generations[index] == entity.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.generations.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.generations.iter().enumerate()
+            .filter(|(index, _)| self.alive[*index])
+            .map(|(index, generation)| Entity { index: index as u32, generation: *generation })
+    }
+
+    /// Drops components belonging to despawned entities from one of your own maps.
+    pub fn retain_alive<T>(&self, map: &mut ComponentMap<T>) {
+        for (index, entry) in map.entries.iter_mut().enumerate() {
+            let stale = entry.as_ref().is_some_and(|(generation, _)| {
+                !self.is_alive(Entity { index: index as u32, generation: *generation })
+            });
+            if stale {
+                *entry = None;
+            }
+        }
+    }
+
+    /// Moves every entity with a position and velocity by `dt` seconds.
+    pub fn apply_velocities(&mut self, dt: f32) {
+        for (_, position, velocity) in self.positions.iter_mut_with(&self.velocities) {
+            position.x += velocity.dx * dt;
+            position.y += velocity.dy * dt;
+        }
+    }
+
+    /// Draws every entity with a position and sprite, skipping any outside the window.
+    pub fn draw_sprites(&self, window: &mut dyn Window) -> Result<()> {
+        let (width, height) = window.get_size();
+        for (_, position, sprite) in self.positions.iter_with(&self.sprites) {
+            let x = position.x.round();
+            let y = position.y.round();
+            if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+                continue;
+            }
+
+            let mut buf = [0u8; 4];
+            let glyph = sprite.glyph.encode_utf8(&mut buf);
+            window.write_str_maybe_colored(y as u16, x as u16, glyph, sprite.colors)?;
+        }
+        Ok(())
+    }
+}
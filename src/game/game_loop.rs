@@ -3,4 +3,388 @@
     2. Frame rate management
     3. State management
     4. Separation of update and render logic
- */
\ No newline at end of file
+ */
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+struct IntervalTask<S> {
+    interval: Duration,
+    next_run: Instant,
+    task: Box<dyn FnMut(&mut S)>,
+}
+
+/// Background work that runs on its own schedules, separate from input and
+/// rendering, e.g. refreshing data every second while drawing at 60fps.
+///
+/// Call `run` from the app's loop as often as convenient; each task runs when
+/// its interval has passed. A task that falls behind runs once and then waits
+/// a full interval, rather than running repeatedly to catch up.
+pub struct IntervalTasks<S> {
+    tasks: Vec<IntervalTask<S>>,
+}
+
+impl<S> IntervalTasks<S> {
+    pub fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    /// Adds a task that first runs one `interval` from now.
+    pub fn with_task(mut self, interval: Duration, task: impl FnMut(&mut S) + 'static) -> Self {
+        self.add(interval, task);
+        self
+    }
+
+    /// Adds a task that first runs one `interval` from now.
+    pub fn add(&mut self, interval: Duration, task: impl FnMut(&mut S) + 'static) {
+        self.tasks.push(IntervalTask {
+            interval,
+            next_run: Instant::now() + interval,
+            task: Box::new(task),
+        });
+    }
+
+    /// Runs every task that's due.
+    pub fn run(&mut self, state: &mut S) {
+        self.run_at(state, Instant::now());
+    }
+
+    /// Like `run`, treating `now` as the current time.
+    pub fn run_at(&mut self, state: &mut S, now: Instant) {
+        for task in &mut self.tasks {
+            if now >= task.next_run {
+                (task.task)(state);
+                task.next_run = now + task.interval;
+            }
+        }
+    }
+
+    /// How long until the next task is due, e.g. to use as an input poll
+    /// timeout. `None` if there are no tasks.
+    pub fn time_until_next(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.tasks.iter()
+            .map(|task| task.next_run.saturating_duration_since(now))
+            .min()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl<S> Default for IntervalTasks<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Notices frames that take longer than a budget, e.g. to log or show a
+/// warning when an update and render together miss the tick rate.
+///
+/// Wrap each frame's update and render in `measure`, or time them yourself
+/// and pass the duration to `record`.
+pub struct SlowFrameDetector {
+    threshold: Duration,
+    on_slow_frame: Box<dyn FnMut(Duration)>,
+    slow_frames: u64,
+}
+
+impl SlowFrameDetector {
+    /// Calls `on_slow_frame` with the measured duration of every frame that
+    /// takes longer than `threshold`.
+    pub fn new(threshold: Duration, on_slow_frame: impl FnMut(Duration) + 'static) -> Self {
+        Self {
+            threshold,
+            on_slow_frame: Box::new(on_slow_frame),
+            slow_frames: 0,
+        }
+    }
+
+    /// Runs one frame's work and checks how long it took.
+    pub fn measure<R>(&mut self, frame: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = frame();
+        self.record(start.elapsed());
+        result
+    }
+
+    /// Checks a frame duration measured by the caller. Returns true if the
+    /// frame was slow.
+    pub fn record(&mut self, duration: Duration) -> bool {
+        if duration <= self.threshold {
+            return false;
+        }
+        self.slow_frames += 1;
+        (self.on_slow_frame)(duration);
+        true
+    }
+
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = threshold;
+    }
+
+    /// How many slow frames have been seen so far
+    pub fn slow_frames(&self) -> u64 {
+        self.slow_frames
+    }
+}
+
+/// Caps how often an event-driven app redraws, so bursts of input (fast mouse
+/// movement, key repeat) are coalesced into at most one render per frame.
+///
+/// Handle every event as usual and call `request_redraw` when something
+/// changed, then render only when `should_render` says so. Use
+/// `time_until_next_frame` as the input poll timeout so a pending redraw isn't
+/// left waiting for the next event.
+///
+/// Other threads can request redraws through a `RedrawHandle`.
+#[derive(Debug)]
+pub struct FrameLimiter {
+    frame_interval: Duration,
+    last_render: Option<Instant>,
+    redraw_pending: RedrawHandle,
+}
+
+impl FrameLimiter {
+    /// Limits rendering to at most `max_fps` frames per second (at least 1).
+    pub fn new(max_fps: u32) -> Self {
+        Self {
+            frame_interval: Duration::from_secs(1) / max_fps.max(1),
+            last_render: None,
+            redraw_pending: RedrawHandle::default(),
+        }
+    }
+
+    /// Marks the screen as needing a redraw on the next allowed frame.
+    pub fn request_redraw(&mut self) {
+        self.redraw_pending.request_redraw();
+    }
+
+    pub fn is_redraw_pending(&self) -> bool {
+        self.redraw_pending.is_pending()
+    }
+
+    /// Returns a handle that requests redraws from other threads, e.g. when a
+    /// worker finishes a download.
+    ///
+    /// Input polling can't be interrupted, so the request is picked up the
+    /// next time the loop checks `should_render`. Keep the poll timeout short
+    /// (the handlers' poll rate, or a frame interval) while workers are busy.
+    pub fn redraw_handle(&self) -> RedrawHandle {
+        self.redraw_pending.clone()
+    }
+
+    /// Returns true if a redraw was requested and a full frame interval has
+    /// passed since the last render. The redraw then counts as done.
+    pub fn should_render(&mut self) -> bool {
+        self.should_render_at(Instant::now())
+    }
+
+    /// Like `should_render`, treating `now` as the current time.
+    pub fn should_render_at(&mut self, now: Instant) -> bool {
+        if !self.is_redraw_pending() || self.wait_at(now) > Duration::ZERO {
+            return false;
+        }
+        self.redraw_pending.0.store(false, Ordering::Relaxed);
+        self.last_render = Some(now);
+        true
+    }
+
+    /// How long until a pending redraw may render. `None` if no redraw is
+    /// pending, so the app can block on input.
+    pub fn time_until_next_frame(&self) -> Option<Duration> {
+        self.is_redraw_pending().then(|| self.wait_at(Instant::now()))
+    }
+
+    fn wait_at(&self, now: Instant) -> Duration {
+        self.last_render
+            .map(|last| (last + self.frame_interval).saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Requests a redraw from a `FrameLimiter`, from any thread. Get one with
+/// `FrameLimiter::redraw_handle`; clones share the same request.
+#[derive(Debug, Clone, Default)]
+pub struct RedrawHandle(Arc<AtomicBool>);
+
+impl RedrawHandle {
+    pub fn request_redraw(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Delivers messages from worker threads (network responses, file watchers)
+/// to the app's loop, which drains them alongside input each frame.
+///
+/// With `with_redraw_handle`, every message sent also requests a redraw from
+/// the `FrameLimiter` the handle came from.
+pub struct Mailbox<M> {
+    sender: Sender<M>,
+    receiver: Receiver<M>,
+    redraw: Option<RedrawHandle>,
+}
+
+impl<M> Mailbox<M> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            redraw: None,
+        }
+    }
+
+    pub fn with_redraw_handle(mut self, redraw: RedrawHandle) -> Self {
+        self.redraw = Some(redraw);
+        self
+    }
+
+    /// Returns a sender to hand to a worker thread.
+    pub fn sender(&self) -> MailboxSender<M> {
+        MailboxSender {
+            sender: self.sender.clone(),
+            redraw: self.redraw.clone(),
+        }
+    }
+
+    /// Returns the messages received so far, without waiting for more.
+    pub fn drain(&self) -> impl Iterator<Item = M> + '_ {
+        self.receiver.try_iter()
+    }
+}
+
+impl<M> Default for Mailbox<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The sending side of a `Mailbox`. Clone it to share it between threads.
+pub struct MailboxSender<M> {
+    sender: Sender<M>,
+    redraw: Option<RedrawHandle>,
+}
+
+impl<M> MailboxSender<M> {
+    /// Sends a message to the app's loop. Returns false if the mailbox has
+    /// been dropped, e.g. because the app exited.
+    pub fn send(&self, message: M) -> bool {
+        let sent = self.sender.send(message).is_ok();
+        if sent {
+            if let Some(redraw) = &self.redraw {
+                redraw.request_redraw();
+            }
+        }
+        sent
+    }
+}
+
+impl<M> Clone for MailboxSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            redraw: self.redraw.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use super::*;
+
+    #[test]
+    fn tasks_run_on_their_own_intervals() {
+        let mut tasks = IntervalTasks::new()
+            .with_task(Duration::from_millis(100), |count: &mut (u32, u32)| count.0 += 1)
+            .with_task(Duration::from_millis(250), |count: &mut (u32, u32)| count.1 += 1);
+        let start = Instant::now();
+        let mut counts = (0, 0);
+
+        for ms in (0..=500).step_by(50) {
+            tasks.run_at(&mut counts, start + Duration::from_millis(ms));
+        }
+        assert_eq!(counts, (5, 2));
+    }
+
+    #[test]
+    fn only_frames_over_the_threshold_are_reported() {
+        let reported = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&reported);
+        let mut detector = SlowFrameDetector::new(Duration::from_millis(16), move |d| log.borrow_mut().push(d));
+
+        assert!(!detector.record(Duration::from_millis(10)));
+        assert!(!detector.record(Duration::from_millis(16)));
+        assert!(detector.record(Duration::from_millis(40)));
+        assert_eq!(*reported.borrow(), vec![Duration::from_millis(40)]);
+        assert_eq!(detector.slow_frames(), 1);
+    }
+
+    #[test]
+    fn redraws_within_a_frame_are_coalesced() {
+        let mut limiter = FrameLimiter::new(10);
+        let start = Instant::now();
+
+        limiter.request_redraw();
+        assert!(limiter.should_render_at(start));
+
+        // Several events inside the same 100ms frame render once, at the end
+        limiter.request_redraw();
+        limiter.request_redraw();
+        assert!(!limiter.should_render_at(start + Duration::from_millis(30)));
+        assert!(!limiter.should_render_at(start + Duration::from_millis(60)));
+        assert!(limiter.should_render_at(start + Duration::from_millis(100)));
+
+        // Nothing to draw without a request
+        assert!(!limiter.should_render_at(start + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn redraw_handles_work_across_threads() {
+        let mut limiter = FrameLimiter::new(60);
+        let handle = limiter.redraw_handle();
+        assert!(!limiter.should_render());
+
+        std::thread::spawn(move || handle.request_redraw()).join().unwrap();
+        assert!(limiter.is_redraw_pending());
+        assert!(limiter.should_render());
+        assert!(!limiter.is_redraw_pending());
+    }
+
+    #[test]
+    fn mailbox_delivers_messages_and_requests_redraws() {
+        let mut limiter = FrameLimiter::new(60);
+        let mailbox = Mailbox::new().with_redraw_handle(limiter.redraw_handle());
+
+        let sender = mailbox.sender();
+        std::thread::spawn(move || {
+            sender.send("downloaded");
+            sender.send("unpacked");
+        }).join().unwrap();
+
+        assert_eq!(mailbox.drain().collect::<Vec<_>>(), vec!["downloaded", "unpacked"]);
+        assert_eq!(mailbox.drain().count(), 0);
+        assert!(limiter.should_render());
+
+        let sender = mailbox.sender();
+        drop(mailbox);
+        assert!(!sender.send("too late"));
+    }
+}
@@ -0,0 +1,150 @@
+/* Line of sight and field of view over a grid.
+    There's no tile map type yet, so passability is given as a closure,
+    `|x, y| -> bool`, that returns true for cells that can be seen through.
+ */
+
+use std::collections::HashSet;
+
+/// Returns the cells on a straight line from `start` to `end` (both included),
+/// using Bresenham's algorithm.
+///
+/// `line(end, start)` returns the same cells in reverse, so line of sight is
+/// symmetric: if A can see B, B can see A.
+pub fn line(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    // Bresenham picks different cells depending on the direction it walks in,
+    // so always walk from the lower endpoint
+    if end < start {
+        let mut cells = bresenham(end, start);
+        cells.reverse();
+        cells
+    } else {
+        bresenham(start, end)
+    }
+}
+
+fn bresenham(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (mut x, mut y) = (start.0 as i32, start.1 as i32);
+    let (x2, y2) = (end.0 as i32, end.1 as i32);
+
+    let dx = (x2 - x).abs();
+    let dy = -(y2 - y).abs();
+    let step_x = if x < x2 { 1 } else { -1 };
+    let step_y = if y < y2 { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    let mut cells = Vec::with_capacity(dx.max(-dy) as usize + 1);
+    loop {
+        cells.push((x as u16, y as u16));
+        if x == x2 && y == y2 {
+            break;
+        }
+
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+    cells
+}
+
+/// Returns true if nothing blocks the view from `start` to `end`.
+///
+/// Only the cells in between are checked, so a wall at `end` is still visible.
+pub fn has_line_of_sight(is_passable: impl Fn(u16, u16) -> bool, start: (u16, u16), end: (u16, u16)) -> bool {
+    let cells = line(start, end);
+    cells.iter()
+        .skip(1)
+        .take(cells.len().saturating_sub(2))
+        .all(|&(x, y)| is_passable(x, y))
+}
+
+/// Returns every cell visible from `origin` within `radius` cells.
+///
+/// Casts a line to each cell on the edge of the radius and stops at the first
+/// blocking cell, which is included so walls around the visible area show up.
+pub fn field_of_view(
+    is_passable: impl Fn(u16, u16) -> bool,
+    origin: (u16, u16),
+    radius: u16,
+) -> HashSet<(u16, u16)> {
+    let mut visible = HashSet::new();
+    visible.insert(origin);
+
+    let (ox, oy) = (origin.0 as i32, origin.1 as i32);
+    let r = radius as i32;
+    let in_radius = |x: u16, y: u16| {
+        let (dx, dy) = (x as i32 - ox, y as i32 - oy);
+        dx * dx + dy * dy <= r * r
+    };
+
+    let clamp = |value: i32| value.clamp(0, u16::MAX as i32) as u16;
+    let edge = (-r..=r).flat_map(|d| [(d, -r), (d, r), (-r, d), (r, d)]);
+    for (dx, dy) in edge {
+        let target = (clamp(ox + dx), clamp(oy + dy));
+        for (x, y) in line(origin, target).into_iter().skip(1) {
+            if !in_radius(x, y) {
+                break;
+            }
+            visible.insert((x, y));
+            if !is_passable(x, y) {
+                break;
+            }
+        }
+    }
+    visible
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_symmetric() {
+        for (a, b) in [((0, 0), (1, 2)), ((5, 1), (0, 4)), ((3, 3), (9, 4)), ((7, 0), (0, 7))] {
+            let mut back = line(b, a);
+            back.reverse();
+            assert_eq!(line(a, b), back, "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn line_of_sight_is_blocked_by_walls_in_between() {
+        let wall = |x: u16, y: u16| (x, y) != (2, 2);
+        assert!(!has_line_of_sight(wall, (0, 0), (4, 4)));
+        assert!(!has_line_of_sight(wall, (4, 4), (0, 0)));
+        assert!(has_line_of_sight(wall, (0, 0), (4, 0)));
+        // The wall itself can be seen
+        assert!(has_line_of_sight(wall, (0, 0), (2, 2)));
+    }
+
+    #[test]
+    fn start_equal_to_end() {
+        assert_eq!(line((3, 4), (3, 4)), vec![(3, 4)]);
+        assert!(has_line_of_sight(|_, _| false, (3, 4), (3, 4)));
+    }
+
+    #[test]
+    fn field_of_view_at_the_map_edges() {
+        let open = |_, _| true;
+        let expected = |origin: (u16, u16)| {
+            let (ox, oy) = (origin.0 as i32, origin.1 as i32);
+            (-2..=2).flat_map(|dy| (-2..=2).map(move |dx| (dx, dy)))
+                .filter(|(dx, dy)| dx * dx + dy * dy <= 4)
+                .map(|(dx, dy)| (ox + dx, oy + dy))
+                .filter(|&(x, y)| (0..=u16::MAX as i32).contains(&x) && (0..=u16::MAX as i32).contains(&y))
+                .map(|(x, y)| (x as u16, y as u16))
+                .collect::<HashSet<_>>()
+        };
+
+        for origin in [(0, 0), (u16::MAX, u16::MAX), (0, u16::MAX), (1, 0)] {
+            assert_eq!(field_of_view(open, origin, 2), expected(origin), "{origin:?}");
+        }
+        assert_eq!(field_of_view(open, (0, 0), 0), HashSet::from([(0, 0)]));
+    }
+}
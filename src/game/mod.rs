@@ -2,4 +2,16 @@ mod tile;
 mod sprite;
 mod collision;
 mod map;
-mod game_loop;
\ No newline at end of file
+mod game_loop;
+mod rng;
+mod entity;
+mod particles;
+pub mod los;
+#[cfg(feature = "serde")]
+pub mod save;
+
+pub use collision::{collides, Body, BodyId, CollisionWorld, SpatialHash};
+pub use entity::{ComponentMap, Entity, EntityStore, Position, Sprite, Velocity};
+pub use game_loop::{FrameLimiter, IntervalTasks, Mailbox, MailboxSender, RedrawHandle, SlowFrameDetector};
+pub use particles::{EmitRate, Emitter, EmitterId, Particle, ParticleSystem, DEFAULT_PARTICLE_GLYPH};
+pub use rng::{GameRng, RangeInt, SampleRange};
@@ -0,0 +1,291 @@
+/* A simple particle system for explosions, sparks, trails and the like.
+    Particles live in cell space with floating point positions, so they can
+    move smoothly at any speed, and are drawn at the nearest cell.
+ */
+
+use crate::game::GameRng;
+use crate::{Color, ColorPair, Result, Window};
+
+/// The glyph drawn for particles with no glyphs of their own
+pub const DEFAULT_PARTICLE_GLYPH: char = '*';
+
+/// A single moving glyph with a limited lifetime.
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    /// Velocity in cells per second
+    pub vx: f32,
+    pub vy: f32,
+    /// Seconds since the particle was spawned
+    pub age: f32,
+    /// Seconds the particle lives for
+    pub lifetime: f32,
+    /// Glyphs shown over the particle's life, e.g. `['*', '+', '.']` to fade
+    /// out. An empty list shows `DEFAULT_PARTICLE_GLYPH`.
+    pub glyphs: Vec<char>,
+    pub colors: ColorPair,
+}
+
+impl Particle {
+    pub fn is_alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+
+    /// The glyph for the particle's current age
+    pub fn glyph(&self) -> char {
+        let progress = if self.lifetime > 0.0 { self.age / self.lifetime } else { 1.0 };
+        let index = (progress * self.glyphs.len() as f32) as usize;
+        self.glyphs.get(index.min(self.glyphs.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or(DEFAULT_PARTICLE_GLYPH)
+    }
+}
+
+/// How an emitter spawns particles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmitRate {
+    /// Spawns this many particles at once, then the emitter is removed
+    Burst(usize),
+    /// Spawns this many particles per second until the emitter is removed
+    Continuous(f32),
+}
+
+/// Spawns particles from a point with randomized direction and speed.
+#[derive(Debug, Clone)]
+pub struct Emitter {
+    pub x: f32,
+    pub y: f32,
+    pub rate: EmitRate,
+    /// Center direction in radians (0 is right, PI/2 is down)
+    pub direction: f32,
+    /// Total angle in radians that particles are spread over
+    pub spread: f32,
+    /// Speed range in cells per second
+    pub speed: (f32, f32),
+    /// Lifetime range in seconds
+    pub lifetime: (f32, f32),
+    /// Glyphs spawned particles cycle through. An empty list spawns
+    /// particles that show `DEFAULT_PARTICLE_GLYPH`.
+    pub glyphs: Vec<char>,
+    pub colors: ColorPair,
+    accumulator: f32,
+}
+
+impl Emitter {
+    fn new(x: f32, y: f32, rate: EmitRate) -> Self {
+        Self {
+            x,
+            y,
+            rate,
+            direction: 0.0,
+            spread: std::f32::consts::TAU,
+            speed: (4.0, 12.0),
+            lifetime: (0.4, 0.8),
+            glyphs: vec!['*', '+', '.'],
+            colors: ColorPair::new(Color::Yellow, Color::Transparent),
+            accumulator: 0.0,
+        }
+    }
+
+    /// An emitter that spawns `count` particles in all directions at once.
+    pub fn burst(x: f32, y: f32, count: usize) -> Self {
+        Self::new(x, y, EmitRate::Burst(count))
+    }
+
+    /// An emitter that keeps spawning `per_second` particles.
+    pub fn continuous(x: f32, y: f32, per_second: f32) -> Self {
+        Self::new(x, y, EmitRate::Continuous(per_second))
+    }
+
+    pub fn with_direction(mut self, direction: f32, spread: f32) -> Self {
+        self.direction = direction;
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = (min, max.max(min));
+        self
+    }
+
+    pub fn with_lifetime(mut self, min: f32, max: f32) -> Self {
+        self.lifetime = (min, max.max(min));
+        self
+    }
+
+    /// Sets the glyphs particles cycle through over their lifetime. Empty
+    /// lists are ignored.
+    pub fn with_glyphs(mut self, glyphs: &[char]) -> Self {
+        if !glyphs.is_empty() {
+            self.glyphs = glyphs.to_vec();
+        }
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorPair) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    fn spawn(&self, rng: &mut GameRng) -> Particle {
+        let angle = self.direction + (rng.next_f32() - 0.5) * self.spread;
+        let speed = lerp(self.speed.0, self.speed.1, rng.next_f32());
+        Particle {
+            x: self.x,
+            y: self.y,
+            vx: angle.cos() * speed,
+            vy: angle.sin() * speed,
+            age: 0.0,
+            lifetime: lerp(self.lifetime.0, self.lifetime.1, rng.next_f32()),
+            glyphs: self.glyphs.clone(),
+            colors: self.colors,
+        }
+    }
+}
+
+/// Identifies an emitter added to a `ParticleSystem`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmitterId(u64);
+
+/// Owns particles and emitters, and advances and draws them each frame.
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    emitters: Vec<(EmitterId, Emitter)>,
+    next_id: u64,
+    /// Added to each particle's vertical velocity, in cells per second squared
+    gravity: f32,
+    rng: GameRng,
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    /// Creates a system whose randomness is reproducible from `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            particles: Vec::new(),
+            emitters: Vec::new(),
+            next_id: 0,
+            gravity: 0.0,
+            rng: GameRng::from_seed(seed),
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Adds an emitter. Burst emitters fire on the next `tick` and are then
+    /// removed; continuous emitters run until `remove_emitter` is called.
+    pub fn add_emitter(&mut self, emitter: Emitter) -> EmitterId {
+        let id = EmitterId(self.next_id);
+        self.next_id += 1;
+        self.emitters.push((id, emitter));
+        id
+    }
+
+    pub fn remove_emitter(&mut self, id: EmitterId) -> Option<Emitter> {
+        let index = self.emitters.iter().position(|(emitter_id, _)| *emitter_id == id)?;
+        Some(self.emitters.remove(index).1)
+    }
+
+    /// Returns an emitter so it can be moved or adjusted, e.g. to follow a player.
+    pub fn emitter_mut(&mut self, id: EmitterId) -> Option<&mut Emitter> {
+        self.emitters.iter_mut()
+            .find(|(emitter_id, _)| *emitter_id == id)
+            .map(|(_, emitter)| emitter)
+    }
+
+    /// Adds a single particle directly.
+    pub fn spawn(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Returns true if there are no live particles or emitters left.
+    pub fn is_finished(&self) -> bool {
+        self.particles.is_empty() && self.emitters.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.emitters.clear();
+    }
+
+    /// Advances the simulation by `dt` seconds: runs emitters, moves and ages
+    /// particles, and removes dead ones.
+    pub fn tick(&mut self, dt: f32) {
+        let rng = &mut self.rng;
+        let particles = &mut self.particles;
+
+        self.emitters.retain_mut(|(_, emitter)| match emitter.rate {
+            EmitRate::Burst(count) => {
+                particles.extend((0..count).map(|_| emitter.spawn(rng)));
+                false
+            }
+            EmitRate::Continuous(per_second) => {
+                emitter.accumulator += per_second * dt;
+                while emitter.accumulator >= 1.0 {
+                    emitter.accumulator -= 1.0;
+                    particles.push(emitter.spawn(rng));
+                }
+                true
+            }
+        });
+
+        for particle in self.particles.iter_mut() {
+            particle.vy += self.gravity * dt;
+            particle.x += particle.vx * dt;
+            particle.y += particle.vy * dt;
+            particle.age += dt;
+        }
+        self.particles.retain(Particle::is_alive);
+    }
+
+    /// Draws every live particle, skipping any that are outside the window.
+    pub fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let (width, height) = window.get_size();
+        for particle in &self.particles {
+            let x = particle.x.round();
+            let y = particle.y.round();
+            if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+                continue;
+            }
+
+            let mut buf = [0u8; 4];
+            let glyph = particle.glyph().encode_utf8(&mut buf);
+            window.write_str_colored(y as u16, x as u16, glyph, particle.colors)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_glyphs_fall_back_to_default() {
+        let mut emitter = Emitter::burst(0.0, 0.0, 1);
+        emitter.glyphs.clear();
+        let particle = emitter.spawn(&mut GameRng::from_seed(1));
+        assert_eq!(particle.glyph(), DEFAULT_PARTICLE_GLYPH);
+    }
+}
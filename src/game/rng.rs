@@ -0,0 +1,211 @@
+/* Seedable random number generation for games.
+    Uses xorshift64* seeded through splitmix64, which is fast and produces the
+    same sequence for the same seed on every platform. It's good enough for
+    games and procedural generation, NOT for cryptography.
+ */
+
+use std::ops::{Range, RangeInclusive};
+
+/// A small, reproducible pseudo-random number generator.
+#[derive(Debug, Clone)]
+pub struct GameRng {
+    state: u64,
+}
+
+impl GameRng {
+    /// Creates a generator that always produces the same sequence for `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        // Run the seed through splitmix64 so that similar seeds (0, 1, 2...)
+        // still give unrelated sequences, and so the state is never zero.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self {
+            state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a float in `0.0..1.0`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a float in `0.0..1.0`
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns true with the given probability (0.0 never, 1.0 always)
+    pub fn gen_bool(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    /// Returns a random integer in `range`, e.g. `rng.gen_range(0..10)` or
+    /// `rng.gen_range(1..=6)`. Every value in the range is equally likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn gen_range<T: RangeInt, R: SampleRange<T>>(&mut self, range: R) -> T {
+        let (low, high) = range.bounds();
+        assert!(low <= high, "gen_range called with an empty range");
+
+        let span = (high - low) as u128 + 1;
+        let offset = match u64::try_from(span) {
+            Ok(span) => self.next_below(span),
+            // The full 64-bit range, which every output already covers
+            Err(_) => self.next_u64(),
+        };
+        T::from_i128(low + offset as i128)
+    }
+
+    /// Returns a value in `0..span` without modulo bias, by rejecting the
+    /// few outputs at the bottom that would make some values more likely.
+    fn next_below(&mut self, span: u64) -> u64 {
+        // 2^64 % span: how many outputs to throw away so the rest divide evenly
+        let rejected = span.wrapping_neg() % span;
+        loop {
+            let value = self.next_u64();
+            if value >= rejected {
+                return value % span;
+            }
+        }
+    }
+
+    /// Returns a random element of `items`, or `None` if it's empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.gen_range(0..items.len()))
+    }
+
+    /// Shuffles `items` in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(0..=i);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Integer types that `GameRng::gen_range` can produce.
+pub trait RangeInt: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_range_int {
+    ($($t:ty),*) => {
+        $(
+            impl RangeInt for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_range_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Ranges accepted by `GameRng::gen_range`.
+pub trait SampleRange<T> {
+    /// Returns the inclusive (low, high) bounds of the range
+    fn bounds(self) -> (i128, i128);
+}
+
+impl<T: RangeInt> SampleRange<T> for Range<T> {
+    fn bounds(self) -> (i128, i128) {
+        (self.start.to_i128(), self.end.to_i128() - 1)
+    }
+}
+
+impl<T: RangeInt> SampleRange<T> for RangeInclusive<T> {
+    fn bounds(self) -> (i128, i128) {
+        (self.start().to_i128(), self.end().to_i128())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(seed: u64) -> Vec<u64> {
+        let mut rng = GameRng::from_seed(seed);
+        (0..16).map(|_| rng.next_u64()).collect()
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        assert_eq!(sequence(42), sequence(42));
+        assert_ne!(sequence(0), sequence(1));
+        assert_ne!(sequence(42), sequence(43));
+    }
+
+    #[test]
+    fn gen_range_stays_in_bounds() {
+        let mut rng = GameRng::from_seed(7);
+        for _ in 0..1000 {
+            assert!((0..10).contains(&rng.gen_range(0..10u32)));
+            assert!((1..=6).contains(&rng.gen_range(1..=6u8)));
+            assert!((-3..3).contains(&rng.gen_range(-3..3i32)));
+            assert!((i8::MIN..=i8::MAX).contains(&rng.gen_range(i8::MIN..=i8::MAX)));
+            // The full range can't go out of bounds, but mustn't overflow either
+            rng.gen_range(0..=u64::MAX);
+            rng.gen_range(i64::MIN..=i64::MAX);
+        }
+
+        assert_eq!(rng.gen_range(5..=5u16), 5);
+        assert_eq!(rng.gen_range(5..6u16), 5);
+    }
+
+    #[test]
+    fn gen_range_reaches_every_value() {
+        let mut rng = GameRng::from_seed(3);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            seen[rng.gen_range(0..6usize)] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn gen_range_rejects_empty_ranges() {
+        GameRng::from_seed(0).gen_range(3..3u8);
+    }
+
+    #[test]
+    fn shuffle_is_a_deterministic_permutation() {
+        let shuffled = |seed| {
+            let mut items: Vec<u32> = (0..20).collect();
+            GameRng::from_seed(seed).shuffle(&mut items);
+            items
+        };
+
+        assert_eq!(shuffled(9), shuffled(9));
+        assert_ne!(shuffled(9), (0..20).collect::<Vec<_>>());
+
+        let mut sorted = shuffled(9);
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,191 @@
+/* Save/load for game state, available with the `serde` feature.
+    Saves are JSON files wrapped in a small header:
+        { "format": "minui-save", "format_version": 1, "version": <game's version>, "state": ... }
+    The header is checked before the state is deserialized, so loading a save
+    from a different version of the game gives a clear error instead of a
+    confusing deserialization failure.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{Error, Result};
+
+const SAVE_FORMAT: &str = "minui-save";
+const FORMAT_VERSION: u64 = 1;
+
+/// Saves `state` to `path` with a state version of 0, the same as
+/// `save_state_versioned(path, 0, state)`.
+pub fn save_state<S: Serialize>(path: impl AsRef<Path>, state: &S) -> Result<()> {
+    save_state_versioned(path, 0, state)
+}
+
+/// Loads a state saved with `save_state`. Saves written with a state version
+/// other than 0 are rejected, like `load_state_versioned(path, 0)`.
+pub fn load_state<S: DeserializeOwned>(path: impl AsRef<Path>) -> Result<S> {
+    load_state_versioned(path, 0)
+}
+
+/// Saves `state` to `path`, tagged with the game's own state `version`.
+///
+/// The file is written to a temporary path first and then renamed, so a crash
+/// mid-save doesn't corrupt an existing save.
+pub fn save_state_versioned<S: Serialize>(path: impl AsRef<Path>, version: u32, state: &S) -> Result<()> {
+    let path = path.as_ref();
+    let state = serde_json::to_value(state)
+        .map_err(|e| Error::SaveError(format!("failed to serialize state: {}", e)))?;
+
+    let contents = json!({
+        "format": SAVE_FORMAT,
+        "format_version": FORMAT_VERSION,
+        "version": version,
+        "state": state,
+    });
+    let contents = serde_json::to_vec_pretty(&contents)
+        .map_err(|e| Error::SaveError(format!("failed to serialize state: {}", e)))?;
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Loads a state saved with `save_state_versioned`, failing with
+/// `Error::SaveError` if the save's version doesn't match `version`.
+///
+/// Use `read_save_version` first if you want to migrate older saves instead.
+pub fn load_state_versioned<S: DeserializeOwned>(path: impl AsRef<Path>, version: u32) -> Result<S> {
+    let (saved_version, state) = read_save(path.as_ref())?;
+
+    if saved_version > version {
+        return Err(Error::SaveError(format!(
+            "save was written by a newer version ({}) than this game supports ({})",
+            saved_version, version
+        )));
+    }
+    if saved_version < version {
+        return Err(Error::SaveError(format!(
+            "save is from an older version ({}) and can't be loaded by version {}",
+            saved_version, version
+        )));
+    }
+
+    serde_json::from_value(state)
+        .map_err(|e| Error::SaveError(format!("failed to load state: {}", e)))
+}
+
+/// Returns the state version a save was written with, without loading it.
+pub fn read_save_version(path: impl AsRef<Path>) -> Result<u32> {
+    read_save(path.as_ref()).map(|(version, _)| version)
+}
+
+/// Reads and validates the save header, returning the state version and the raw state.
+fn read_save(path: &Path) -> Result<(u32, Value)> {
+    let contents = fs::read(path)?;
+    let mut save: Value = serde_json::from_slice(&contents)
+        .map_err(|e| Error::SaveError(format!("not a valid save file: {}", e)))?;
+
+    if save.get("format").and_then(Value::as_str) != Some(SAVE_FORMAT) {
+        return Err(Error::SaveError("not a minui save file".to_string()));
+    }
+
+    match save.get("format_version").and_then(Value::as_u64) {
+        Some(FORMAT_VERSION) => {}
+        Some(other) => {
+            return Err(Error::SaveError(format!("unsupported save format version {}", other)));
+        }
+        None => return Err(Error::SaveError("save file is missing its format version".to_string())),
+    }
+
+    let version = save.get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| Error::SaveError("save file is missing its version".to_string()))?;
+    let state = save.get_mut("state")
+        .map(Value::take)
+        .ok_or_else(|| Error::SaveError("save file has no state".to_string()))?;
+
+    Ok((version, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use super::*;
+
+    /// A fresh directory under the system temp dir, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("minui-save-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    type State = (String, Vec<u32>);
+
+    fn state() -> State {
+        ("level 3".to_string(), vec![1, 2, 3])
+    }
+
+    #[test]
+    fn round_trips_and_cleans_up() {
+        let dir = TempDir::new("round-trip");
+        let path = dir.0.join("game.json");
+
+        save_state(&path, &state()).unwrap();
+        assert_eq!(load_state::<State>(&path).unwrap(), state());
+        assert_eq!(read_save_version(&path).unwrap(), 0);
+
+        save_state_versioned(&path, 4, &state()).unwrap();
+        assert_eq!(load_state_versioned::<State>(&path, 4).unwrap(), state());
+
+        let files: Vec<_> = fs::read_dir(&dir.0).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(files, vec!["game.json"]);
+    }
+
+    #[test]
+    fn version_mismatches_are_errors() {
+        let dir = TempDir::new("mismatch");
+        let path = dir.0.join("game.json");
+        save_state_versioned(&path, 2, &state()).unwrap();
+
+        assert!(matches!(load_state_versioned::<State>(&path, 1), Err(Error::SaveError(_))));
+        assert!(matches!(load_state_versioned::<State>(&path, 3), Err(Error::SaveError(_))));
+        assert!(matches!(load_state::<State>(&path), Err(Error::SaveError(_))));
+    }
+
+    #[test]
+    fn corrupt_saves_are_errors() {
+        let dir = TempDir::new("corrupt");
+        let path = dir.0.join("game.json");
+        save_state(&path, &state()).unwrap();
+
+        let contents = fs::read(&path).unwrap();
+        fs::write(&path, &contents[..contents.len() / 2]).unwrap();
+        assert!(matches!(load_state::<State>(&path), Err(Error::SaveError(_))));
+
+        fs::write(&path, br#"{"format": "something-else", "state": null}"#).unwrap();
+        assert!(matches!(load_state::<State>(&path), Err(Error::SaveError(_))));
+
+        // Valid header, but a state of the wrong shape
+        save_state(&path, &42).unwrap();
+        assert!(matches!(load_state::<State>(&path), Err(Error::SaveError(_))));
+
+        assert!(matches!(load_state::<State>(dir.0.join("missing.json")), Err(Error::IOError(_))));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Event;
+
+enum Binding {
+    Exact(Event),
+    Matching(Box<dyn Fn(&Event) -> bool>),
+}
+
+impl Binding {
+    fn matches(&self, event: &Event) -> bool {
+        match self {
+            Binding::Exact(bound) => bound == event,
+            Binding::Matching(predicate) => predicate(event),
+        }
+    }
+}
+
+/// Maps input events to application-defined actions.
+///
+/// Bindings are checked in the order they were added, and the first match wins.
+pub struct ActionMap<A> {
+    bindings: Vec<(Binding, A)>,
+}
+
+impl<A: Clone> ActionMap<A> {
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Binds an exact event to an action
+    pub fn bind(&mut self, event: Event, action: A) {
+        self.bindings.push((Binding::Exact(event), action));
+    }
+
+    /// Binds every event matching `predicate` to an action, e.g. any left
+    /// click regardless of position.
+    pub fn bind_with(&mut self, predicate: impl Fn(&Event) -> bool + 'static, action: A) {
+        self.bindings.push((Binding::Matching(Box::new(predicate)), action));
+    }
+
+    /// Removes all exact bindings for `event`
+    pub fn unbind(&mut self, event: &Event) {
+        self.bindings.retain(|(binding, _)| !matches!(binding, Binding::Exact(bound) if bound == event));
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Returns the action bound to `event`, if any
+    pub fn resolve(&self, event: &Event) -> Option<A> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(event))
+            .map(|(_, action)| action.clone())
+    }
+}
+
+impl<A: Clone> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::{Duration, Instant};
+use crate::Event;
+
+/// The outcome of feeding an event to a `ChordRecognizer`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChordMatch<A> {
+    /// A full chord was recognized
+    Action(A),
+    /// The event was buffered as the start of one or more chords
+    Pending,
+    /// These events aren't part of any chord and should be handled normally
+    Unmatched(Vec<Event>),
+}
+
+/// Recognizes multi-key sequences like vim's `gg` or `dd`.
+///
+/// Events are buffered while they form the start of a registered chord. When
+/// a sequence both completes one chord and starts a longer one (`g` and `gg`),
+/// the recognizer waits: the next key or the timeout decides. Call `expire`
+/// regularly (e.g. once per frame) so timed-out sequences are reported even if
+/// no further key arrives; they come back as the chord they complete, or as
+/// `Unmatched` keys.
+pub struct ChordRecognizer<A> {
+    chords: Vec<(Vec<Event>, A)>,
+    pending: Vec<Event>,
+    last_event: Option<Instant>,
+    timeout: Duration,
+}
+
+impl<A: Clone> ChordRecognizer<A> {
+    pub fn new() -> Self {
+        Self {
+            chords: Vec::new(),
+            pending: Vec::new(),
+            last_event: None,
+            timeout: Duration::from_millis(1000),
+        }
+    }
+
+    /// Set how long to wait for the next key of a chord
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn register_chord(&mut self, keys: &[Event], action: A) {
+        if !keys.is_empty() {
+            self.chords.push((keys.to_vec(), action));
+        }
+    }
+
+    /// The keys buffered so far for a partially typed chord
+    pub fn pending(&self) -> &[Event] {
+        &self.pending
+    }
+
+    /// Drops any partially typed chord
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_event = None;
+    }
+
+    /// Feeds an event, returning what it (and any timed-out keys before it)
+    /// resolved to, in order.
+    pub fn feed(&mut self, event: Event) -> Vec<ChordMatch<A>> {
+        self.feed_at(event, Instant::now())
+    }
+
+    /// Like `feed`, but with an explicit timestamp for the event.
+    pub fn feed_at(&mut self, event: Event, now: Instant) -> Vec<ChordMatch<A>> {
+        let mut results = self.expire_at(now);
+
+        self.pending.push(event);
+        self.settle(false, &mut results);
+        if self.pending.is_empty() {
+            self.last_event = None;
+        } else {
+            self.last_event = Some(now);
+            results.push(ChordMatch::Pending);
+        }
+        results
+    }
+
+    /// Resolves a partially typed chord whose timeout has passed. Returns
+    /// nothing if no chord is pending or it hasn't timed out yet.
+    pub fn expire(&mut self) -> Vec<ChordMatch<A>> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Like `expire`, with an explicit current time.
+    pub fn expire_at(&mut self, now: Instant) -> Vec<ChordMatch<A>> {
+        let mut results = Vec::new();
+        if self.last_event.is_some_and(|last| now.duration_since(last) > self.timeout) {
+            self.settle(true, &mut results);
+            self.last_event = None;
+        }
+        results
+    }
+
+    /// Resolves as much of the pending sequence as possible. Unless `force`
+    /// is set, a sequence that could still grow into a longer chord is left
+    /// pending.
+    fn settle(&mut self, force: bool, results: &mut Vec<ChordMatch<A>>) {
+        while !self.pending.is_empty() {
+            if !force && self.is_prefix(&self.pending) {
+                return;
+            }
+            if let Some(action) = self.exact_match(&self.pending) {
+                self.pending.clear();
+                results.push(ChordMatch::Action(action));
+                return;
+            }
+
+            // The sequence is broken. Report the longest chord it starts with,
+            // or else its first key, and try again with the rest.
+            let matched = (1..self.pending.len()).rev()
+                .find_map(|len| self.exact_match(&self.pending[..len]).map(|action| (len, action)));
+            match matched {
+                Some((len, action)) => {
+                    self.pending.drain(..len);
+                    results.push(ChordMatch::Action(action));
+                }
+                None => {
+                    let key = self.pending.remove(0);
+                    match results.last_mut() {
+                        Some(ChordMatch::Unmatched(keys)) => keys.push(key),
+                        _ => results.push(ChordMatch::Unmatched(vec![key])),
+                    }
+                }
+            }
+        }
+    }
+
+    fn exact_match(&self, keys: &[Event]) -> Option<A> {
+        self.chords
+            .iter()
+            .find(|(chord, _)| chord.as_slice() == keys)
+            .map(|(_, action)| action.clone())
+    }
+
+    fn is_prefix(&self, keys: &[Event]) -> bool {
+        self.chords
+            .iter()
+            .any(|(chord, _)| chord.len() > keys.len() && chord.starts_with(keys))
+    }
+}
+
+impl<A: Clone> Default for ChordRecognizer<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recognizer() -> ChordRecognizer<&'static str> {
+        let mut chords = ChordRecognizer::new();
+        chords.register_chord(&[Event::Character('g')], "g");
+        chords.register_chord(&[Event::Character('g'), Event::Character('g')], "gg");
+        chords.register_chord(&[Event::Character('d'), Event::Character('d')], "dd");
+        chords
+    }
+
+    #[test]
+    fn longer_chord_wins_over_its_prefix() {
+        let mut chords = recognizer();
+        let now = Instant::now();
+        assert_eq!(chords.feed_at(Event::Character('g'), now), vec![ChordMatch::Pending]);
+        assert_eq!(chords.feed_at(Event::Character('g'), now), vec![ChordMatch::Action("gg")]);
+    }
+
+    #[test]
+    fn ambiguous_prefix_resolves_on_next_key() {
+        let mut chords = recognizer();
+        let now = Instant::now();
+        chords.feed_at(Event::Character('g'), now);
+        assert_eq!(
+            chords.feed_at(Event::Character('x'), now),
+            vec![ChordMatch::Action("g"), ChordMatch::Unmatched(vec![Event::Character('x')])]
+        );
+    }
+
+    #[test]
+    fn ambiguous_prefix_resolves_on_timeout() {
+        let mut chords = recognizer();
+        let now = Instant::now();
+        chords.feed_at(Event::Character('g'), now);
+        assert!(chords.expire_at(now).is_empty());
+        assert_eq!(chords.expire_at(now + Duration::from_secs(2)), vec![ChordMatch::Action("g")]);
+        assert!(chords.pending().is_empty());
+    }
+
+    #[test]
+    fn timed_out_prefix_is_returned_before_the_next_key() {
+        let mut chords = recognizer();
+        let now = Instant::now();
+        chords.feed_at(Event::Character('d'), now);
+        assert_eq!(
+            chords.feed_at(Event::Character('d'), now + Duration::from_secs(2)),
+            vec![ChordMatch::Unmatched(vec![Event::Character('d')]), ChordMatch::Pending]
+        );
+    }
+}
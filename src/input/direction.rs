@@ -0,0 +1,118 @@
+use crate::Event;
+
+/// One of the four movement directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Maps arrow keys, WASD and hjkl to a direction.
+    ///
+    /// Use `DirectionKeys` to enable only some of these schemes.
+    pub fn from_event(event: &Event) -> Option<Direction> {
+        DirectionKeys::all().resolve(event)
+    }
+
+    /// The (dx, dy) step for this direction, with y growing downwards
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// Chooses which key schemes map to directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectionKeys {
+    /// Arrow keys
+    pub arrows: bool,
+    /// W/A/S/D, in either case
+    pub wasd: bool,
+    /// Vim-style h/j/k/l
+    pub vim: bool,
+}
+
+impl DirectionKeys {
+    /// Enables every scheme
+    pub const fn all() -> Self {
+        Self {
+            arrows: true,
+            wasd: true,
+            vim: true,
+        }
+    }
+
+    /// Enables only the arrow keys, leaving letters free for text and commands
+    pub const fn arrows_only() -> Self {
+        Self {
+            arrows: true,
+            wasd: false,
+            vim: false,
+        }
+    }
+
+    pub fn with_arrows(mut self, enabled: bool) -> Self {
+        self.arrows = enabled;
+        self
+    }
+
+    pub fn with_wasd(mut self, enabled: bool) -> Self {
+        self.wasd = enabled;
+        self
+    }
+
+    pub fn with_vim(mut self, enabled: bool) -> Self {
+        self.vim = enabled;
+        self
+    }
+
+    /// Returns the direction for `event` under the enabled schemes.
+    pub fn resolve(&self, event: &Event) -> Option<Direction> {
+        match *event {
+            Event::KeyUp if self.arrows => Some(Direction::Up),
+            Event::KeyDown if self.arrows => Some(Direction::Down),
+            Event::KeyLeft if self.arrows => Some(Direction::Left),
+            Event::KeyRight if self.arrows => Some(Direction::Right),
+            Event::Character(c) => {
+                let wasd = match c.to_ascii_lowercase() {
+                    'w' => Some(Direction::Up),
+                    's' => Some(Direction::Down),
+                    'a' => Some(Direction::Left),
+                    'd' => Some(Direction::Right),
+                    _ => None,
+                };
+                let vim = match c {
+                    'k' => Some(Direction::Up),
+                    'j' => Some(Direction::Down),
+                    'h' => Some(Direction::Left),
+                    'l' => Some(Direction::Right),
+                    _ => None,
+                };
+                wasd.filter(|_| self.wasd).or(vim.filter(|_| self.vim))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for DirectionKeys {
+    fn default() -> Self {
+        Self::all()
+    }
+}
@@ -1,15 +1,51 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::io::{stdout, Write};
 use std::time::Duration;
-use crossterm::event::{self, Event as CrosstermEvent, KeyCode};
+use crossterm::event::{
+    self, Event as CrosstermEvent, KeyEventKind, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::{execute, terminal};
 use crate::{Event, Result};
+use super::{modes, KeyTransition};
 
 pub struct KeyboardHandler {
     poll_rate: Duration,
+    enhancements: KeyboardEnhancementFlags,
+    stats: Cell<InputStats>,
+    writer: Box<dyn Write>,
+    injected: RefCell<VecDeque<Event>>,
+}
+
+/// Counts of terminal events seen by a `KeyboardHandler`, for diagnosing lost input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputStats {
+    /// Events read from the terminal
+    pub polled: u64,
+    /// Events returned to the caller
+    pub dispatched: u64,
+    /// Events read but discarded: the pending events `poll` drains after a
+    /// key, and non-key events like mouse moves
+    pub dropped: u64,
 }
 
 impl KeyboardHandler {
     pub fn new() -> Self {
+        Self::with_writer(stdout())
+    }
+
+    /// Creates a handler that sends keyboard protocol requests (for release
+    /// events and key disambiguation) to `writer` instead of stdout. Use the
+    /// same kind of output as the `TerminalWindow`, e.g. stderr for a window
+    /// created with `TerminalWindow::with_writer(stderr())`.
+    pub fn with_writer(writer: impl Write + 'static) -> Self {
         Self {
             poll_rate: Duration::from_millis(1),
+            enhancements: KeyboardEnhancementFlags::empty(),
+            stats: Cell::new(InputStats::default()),
+            writer: Box::new(writer),
+            injected: RefCell::new(VecDeque::new()),
         }
     }
 
@@ -23,37 +59,192 @@ impl KeyboardHandler {
         self.poll_rate
     }
 
+    /// Returns true if the terminal supports the kitty keyboard protocol,
+    /// which key release events and key disambiguation rely on.
+    pub fn supports_enhancements() -> bool {
+        terminal::supports_keyboard_enhancement().unwrap_or(false)
+    }
+
+    /// Asks the terminal to report key releases (and repeats), using the kitty
+    /// keyboard protocol.
+    ///
+    /// Returns false if the terminal doesn't support it. In that case
+    /// `poll_transitions` only reports presses, and `InputState` falls back to
+    /// treating keys as released after a timeout.
+    pub fn enable_release_events(&mut self) -> Result<bool> {
+        self.enable_flags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+    }
+
+    /// Stops key release reporting, if it was enabled.
+    pub fn disable_release_events(&mut self) -> Result<()> {
+        self.set_flags(self.enhancements - KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+    }
+
+    /// Returns true if the terminal is reporting key releases
+    pub fn release_events_enabled(&self) -> bool {
+        self.enhancements.contains(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+    }
+
+    /// Asks the terminal to report control chords distinctly from the keys
+    /// they normally collapse into, so Ctrl+M, Ctrl+I and Ctrl+[ arrive as
+    /// `Event::KeyWithModifiers` rather than Enter, Tab and Escape.
+    ///
+    /// Returns false if the terminal doesn't support it, in which case those
+    /// chords stay indistinguishable from the plain keys.
+    pub fn enable_key_disambiguation(&mut self) -> Result<bool> {
+        self.enable_flags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+    }
+
+    pub fn disable_key_disambiguation(&mut self) -> Result<()> {
+        self.set_flags(self.enhancements - KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+    }
+
+    pub fn key_disambiguation_enabled(&self) -> bool {
+        self.enhancements.contains(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+    }
+
+    fn enable_flags(&mut self, flags: KeyboardEnhancementFlags) -> Result<bool> {
+        if self.enhancements.contains(flags) {
+            return Ok(true);
+        }
+        if !Self::supports_enhancements() {
+            return Ok(false);
+        }
+
+        self.set_flags(self.enhancements | flags)?;
+        Ok(true)
+    }
+
+    /// Replaces the flags pushed to the terminal. They're kept as a single
+    /// entry on the terminal's flag stack so disabling restores its original state.
+    fn set_flags(&mut self, flags: KeyboardEnhancementFlags) -> Result<()> {
+        if flags == self.enhancements {
+            return Ok(());
+        }
+
+        if !self.enhancements.is_empty() {
+            execute!(self.writer, PopKeyboardEnhancementFlags)?;
+        }
+        if !flags.is_empty() {
+            execute!(self.writer, PushKeyboardEnhancementFlags(flags))?;
+        }
+        self.enhancements = flags;
+        modes::keyboard_flags_changed(flags);
+        Ok(())
+    }
+
+    /// Returns counts of the events polled, dispatched and dropped so far
+    pub fn input_stats(&self) -> InputStats {
+        self.stats.get()
+    }
+
+    pub fn reset_input_stats(&self) {
+        self.stats.set(InputStats::default());
+    }
+
+    fn record(&self, polled: u64, dispatched: u64, dropped: u64) {
+        let mut stats = self.stats.get();
+        stats.polled += polled;
+        stats.dispatched += dispatched;
+        stats.dropped += dropped;
+        self.stats.set(stats);
+    }
+
+    /// Queues a synthetic event, e.g. for scripted demos or tests. `poll`
+    /// returns queued events in order, ahead of any terminal input, and
+    /// without reading from the terminal while any are left.
+    pub fn inject(&self, event: Event) {
+        self.injected.borrow_mut().push_back(event);
+    }
+
+    /// Queues several synthetic events, e.g. `[Event::KeyDown, Event::Character('q')]`.
+    pub fn inject_all(&self, events: impl IntoIterator<Item = Event>) {
+        self.injected.borrow_mut().extend(events);
+    }
+
     /// Poll for keyboard input and return an Option<Event>
     pub fn poll(&self) -> Result<Option<Event>> {
+        if let Some(event) = self.injected.borrow_mut().pop_front() {
+            self.record(0, 1, 0);
+            return Ok(Some(event));
+        }
+
         // Only poll for a very short time to prevent multiple reads
         if event::poll(self.poll_rate)? {
-            if let CrosstermEvent::Key(key) = event::read()? {
-                // Immediately drain any pending events to prevent double-processing
-                while event::poll(Duration::from_millis(0))? {
-                    let _ = event::read()?;
+            let key = match event::read()? {
+                CrosstermEvent::Key(key) if key.kind != KeyEventKind::Release => key,
+                _ => {
+                    self.record(1, 0, 1);
+                    return Ok(None);
                 }
+            };
 
-                return Ok(Some(match key.code {
-                    KeyCode::Char(c) => Event::Character(c),
-                    KeyCode::Up => Event::KeyUp,
-                    KeyCode::Down => Event::KeyDown,
-                    KeyCode::Left => Event::KeyLeft,
-                    KeyCode::Right => Event::KeyRight,
-                    KeyCode::Delete => Event::Delete,
-                    KeyCode::Backspace => Event::Backspace,
-                    KeyCode::Enter => Event::Enter,
-                    KeyCode::F(n) => Event::FunctionKey(n),
-                    KeyCode::Esc => Event::Escape,  // Adding Escape key handling
-                    _ => Event::Unknown,
-                }));
+            // Immediately drain any pending events to prevent double-processing
+            let mut drained = 0;
+            while event::poll(Duration::from_millis(0))? {
+                let _ = event::read()?;
+                drained += 1;
             }
+
+            self.record(1 + drained, 1, drained);
+            return Ok(Some(Event::from_crossterm_key(&key)));
         }
         Ok(None)
     }
+
+    /// Reads every pending key event as a press or release, for tracking held
+    /// keys with `InputState`.
+    ///
+    /// Unlike `poll`, nothing is drained or dropped, since a lost release would
+    /// leave a key stuck down. Key repeats are reported as presses.
+    pub fn poll_transitions(&self) -> Result<Vec<KeyTransition>> {
+        let mut transitions = Vec::new();
+        let mut timeout = self.poll_rate;
+
+        while event::poll(timeout)? {
+            timeout = Duration::from_millis(0);
+            if let CrosstermEvent::Key(key) = event::read()? {
+                let key_event = Event::from_crossterm_key(&key);
+                transitions.push(match key.kind {
+                    KeyEventKind::Release => KeyTransition::Released(key_event),
+                    KeyEventKind::Press | KeyEventKind::Repeat => KeyTransition::Pressed(key_event),
+                });
+                self.record(1, 1, 0);
+            } else {
+                self.record(1, 0, 1);
+            }
+        }
+        Ok(transitions)
+    }
 }
 
 impl Default for KeyboardHandler {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+impl Drop for KeyboardHandler {
+    fn drop(&mut self) {
+        let _ = self.set_flags(KeyboardEnhancementFlags::empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injected_events_are_polled_in_order() {
+        let keyboard = KeyboardHandler::new();
+        keyboard.inject_all([Event::KeyDown, Event::KeyDown]);
+        keyboard.inject(Event::Character('q'));
+
+        let mut polled = Vec::new();
+        for _ in 0..3 {
+            polled.push(keyboard.poll().unwrap());
+        }
+        assert_eq!(polled, vec![Some(Event::KeyDown), Some(Event::KeyDown), Some(Event::Character('q'))]);
+        assert_eq!(keyboard.input_stats().dispatched, 3);
+    }
+}
@@ -1,4 +1,16 @@
+mod action_map;
+mod chord;
+mod direction;
 mod keyboard;
+mod modal;
+pub(crate) mod modes;
 mod mouse;
+mod state;
 
-pub use keyboard::{KeyboardHandler};
\ No newline at end of file
+pub use action_map::ActionMap;
+pub use chord::{ChordMatch, ChordRecognizer};
+pub use direction::{Direction, DirectionKeys};
+pub use keyboard::{InputStats, KeyboardHandler};
+pub use modal::{ModalEvent, ModalInput, Mode};
+pub use mouse::MouseHandler;
+pub use state::{InputState, KeyTransition};
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use crate::Event;
+use super::{ChordMatch, ChordRecognizer};
+
+/// Editing modes for vim-style modal input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual,
+    Command,
+}
+
+/// The outcome of feeding an event to `ModalInput`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModalEvent<A> {
+    /// A key sequence bound in the current mode was recognized
+    Action(A),
+    /// The mode changed (e.g. `i` in Normal mode or `Esc` in Insert mode)
+    ModeChanged(Mode),
+    /// A command line was submitted with Enter (without the leading `:`)
+    Command(String),
+    /// Keys that aren't bound in the current mode, such as text typed in
+    /// Insert mode, for the app to handle
+    Keys(Vec<Event>),
+    /// The event was consumed (part of a pending chord or the command line)
+    Pending,
+}
+
+/// Tracks a vim-style editing mode and routes keys differently per mode.
+///
+/// By default `i`, `v` and `:` switch from Normal to Insert, Visual and Command
+/// mode, and `Esc` returns to Normal mode. In Command mode, typed characters
+/// build up a command line that's submitted with Enter.
+pub struct ModalInput<A> {
+    mode: Mode,
+    bindings: HashMap<Mode, ChordRecognizer<A>>,
+    mode_keys: Vec<(Mode, Event, Mode)>,
+    command_line: String,
+}
+
+impl<A: Clone> ModalInput<A> {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Normal,
+            bindings: HashMap::new(),
+            mode_keys: vec![
+                (Mode::Normal, Event::Character('i'), Mode::Insert),
+                (Mode::Normal, Event::Character('v'), Mode::Visual),
+                (Mode::Normal, Event::Character(':'), Mode::Command),
+                (Mode::Insert, Event::Escape, Mode::Normal),
+                (Mode::Visual, Event::Escape, Mode::Normal),
+            ],
+            command_line: String::new(),
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.command_line.clear();
+        for recognizer in self.bindings.values_mut() {
+            recognizer.reset();
+        }
+    }
+
+    /// The command line typed so far in Command mode
+    pub fn command_line(&self) -> &str {
+        &self.command_line
+    }
+
+    /// Binds a key or key sequence to an action in the given mode
+    pub fn bind(&mut self, mode: Mode, keys: &[Event], action: A) {
+        self.bindings.entry(mode).or_default().register_chord(keys, action);
+    }
+
+    /// Makes `key` switch from mode `from` to mode `to`
+    pub fn set_mode_key(&mut self, from: Mode, key: Event, to: Mode) {
+        self.mode_keys.retain(|(mode, bound, _)| !(*mode == from && *bound == key));
+        self.mode_keys.push((from, key, to));
+    }
+
+    /// Removes all mode-switching keys, including the defaults
+    pub fn clear_mode_keys(&mut self) {
+        self.mode_keys.clear();
+    }
+
+    /// Feeds an event, returning what it (and any timed-out chord before it)
+    /// resolved to, in order.
+    pub fn feed(&mut self, event: Event) -> Vec<ModalEvent<A>> {
+        if self.mode == Mode::Command {
+            return vec![self.feed_command(event)];
+        }
+
+        // Settle a timed-out chord first, so it can't hold back a mode switch
+        let mut results = self.expire();
+
+        let chord_pending = self.bindings
+            .get(&self.mode)
+            .is_some_and(|recognizer| !recognizer.pending().is_empty());
+
+        if !chord_pending {
+            let switch = self.mode_keys
+                .iter()
+                .find(|(mode, key, _)| *mode == self.mode && *key == event)
+                .map(|(_, _, to)| *to);
+
+            if let Some(to) = switch {
+                self.set_mode(to);
+                results.push(ModalEvent::ModeChanged(to));
+                return results;
+            }
+        }
+
+        match self.bindings.get_mut(&self.mode) {
+            Some(recognizer) => results.extend(recognizer.feed(event).into_iter().map(ModalEvent::from)),
+            None => results.push(ModalEvent::Keys(vec![event])),
+        }
+        results
+    }
+
+    /// Resolves a partially typed chord in the current mode whose timeout has
+    /// passed. Call this regularly (e.g. once per frame) so a lone prefix key
+    /// doesn't wait for the next key press to be handled.
+    pub fn expire(&mut self) -> Vec<ModalEvent<A>> {
+        match self.bindings.get_mut(&self.mode) {
+            Some(recognizer) => recognizer.expire().into_iter().map(ModalEvent::from).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn feed_command(&mut self, event: Event) -> ModalEvent<A> {
+        match event {
+            Event::Character(c) => {
+                self.command_line.push(c);
+                ModalEvent::Pending
+            }
+            Event::Backspace if !self.command_line.is_empty() => {
+                self.command_line.pop();
+                ModalEvent::Pending
+            }
+            Event::Enter => {
+                let command = std::mem::take(&mut self.command_line);
+                self.set_mode(Mode::Normal);
+                ModalEvent::Command(command)
+            }
+            Event::Escape | Event::Backspace => {
+                self.set_mode(Mode::Normal);
+                ModalEvent::ModeChanged(Mode::Normal)
+            }
+            _ => ModalEvent::Pending,
+        }
+    }
+}
+
+impl<A> From<ChordMatch<A>> for ModalEvent<A> {
+    fn from(chord: ChordMatch<A>) -> Self {
+        match chord {
+            ChordMatch::Action(action) => ModalEvent::Action(action),
+            ChordMatch::Pending => ModalEvent::Pending,
+            ChordMatch::Unmatched(keys) => ModalEvent::Keys(keys),
+        }
+    }
+}
+
+impl<A: Clone> Default for ModalInput<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
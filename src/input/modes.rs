@@ -0,0 +1,32 @@
+//! Terminal input modes turned on by the input handlers, so that
+//! `TerminalWindow::suspend` can turn them off while another program has the
+//! terminal and back on afterwards.
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use crossterm::event::KeyboardEnhancementFlags;
+
+/// How many `MouseHandler`s currently have capture enabled
+static MOUSE_CAPTURES: AtomicUsize = AtomicUsize::new(0);
+
+/// The keyboard enhancement flags most recently pushed by a `KeyboardHandler`
+static KEYBOARD_FLAGS: AtomicU8 = AtomicU8::new(0);
+
+pub(crate) fn mouse_capture_changed(enabled: bool) {
+    if enabled {
+        MOUSE_CAPTURES.fetch_add(1, Ordering::Relaxed);
+    } else {
+        let _ = MOUSE_CAPTURES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| count.checked_sub(1));
+    }
+}
+
+pub(crate) fn mouse_capture_enabled() -> bool {
+    MOUSE_CAPTURES.load(Ordering::Relaxed) > 0
+}
+
+pub(crate) fn keyboard_flags_changed(flags: KeyboardEnhancementFlags) {
+    KEYBOARD_FLAGS.store(flags.bits(), Ordering::Relaxed);
+}
+
+pub(crate) fn keyboard_flags() -> KeyboardEnhancementFlags {
+    KeyboardEnhancementFlags::from_bits_truncate(KEYBOARD_FLAGS.load(Ordering::Relaxed))
+}
@@ -1 +1,192 @@
-// Mouse input support
\ No newline at end of file
+// Mouse input support
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::io::{stdout, Write};
+use std::time::Duration;
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyEventKind,
+    MouseButton as CrosstermMouseButton, MouseEventKind,
+};
+use crossterm::execute;
+use crate::{Event, Result};
+use crate::event::MouseButton;
+use super::{modes, InputStats};
+
+/// Polls for mouse and keyboard events together.
+///
+/// Call `enable_capture` first; terminals only report the mouse once capture
+/// is on.
+pub struct MouseHandler {
+    poll_rate: Duration,
+    capture: bool,
+    coalesce_moves: bool,
+    scroll_lines: u16,
+    stats: Cell<InputStats>,
+    writer: Box<dyn Write>,
+    injected: RefCell<VecDeque<Event>>,
+}
+
+impl MouseHandler {
+    pub fn new() -> Self {
+        Self::with_writer(stdout())
+    }
+
+    /// Creates a handler that sends mouse capture requests to `writer`
+    /// instead of stdout, to match a window created with
+    /// `TerminalWindow::with_writer`.
+    pub fn with_writer(writer: impl Write + 'static) -> Self {
+        Self {
+            poll_rate: Duration::from_millis(1),
+            capture: false,
+            coalesce_moves: true,
+            scroll_lines: 1,
+            stats: Cell::new(InputStats::default()),
+            writer: Box::new(writer),
+            injected: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    /// Set the polling rate for input
+    pub fn set_poll_rate(&mut self, milliseconds: u64) {
+        self.poll_rate = Duration::from_millis(milliseconds);
+    }
+
+    /// Starts reporting mouse events from the terminal.
+    pub fn enable_capture(&mut self) -> Result<()> {
+        if !self.capture {
+            execute!(self.writer, EnableMouseCapture)?;
+            self.capture = true;
+            modes::mouse_capture_changed(true);
+        }
+        Ok(())
+    }
+
+    /// Stops reporting mouse events, so the terminal handles selection again.
+    pub fn disable_capture(&mut self) -> Result<()> {
+        if self.capture {
+            execute!(self.writer, DisableMouseCapture)?;
+            self.capture = false;
+            modes::mouse_capture_changed(false);
+        }
+        Ok(())
+    }
+
+    /// When enabled (the default), a run of `MouseMove` events within one poll
+    /// is collapsed into just the latest position. Clicks, drags, releases,
+    /// scrolls and keys are never coalesced, and a move is kept if anything
+    /// else came between.
+    pub fn set_move_coalescing(&mut self, enabled: bool) {
+        self.coalesce_moves = enabled;
+    }
+
+    pub fn move_coalescing(&self) -> bool {
+        self.coalesce_moves
+    }
+
+    /// Sets how many lines one wheel notch scrolls (at least 1). This is the
+    /// `delta` of each `MouseScroll` event, capped at 127, so it can be passed
+    /// straight to scrolling methods like `TextBlock::scroll_by`.
+    pub fn set_scroll_lines(&mut self, lines: u16) {
+        self.scroll_lines = lines.max(1);
+    }
+
+    pub fn scroll_lines(&self) -> u16 {
+        self.scroll_lines
+    }
+
+    /// Returns counts of the events polled, dispatched and dropped so far.
+    /// Coalesced mouse moves count as dropped.
+    pub fn input_stats(&self) -> InputStats {
+        self.stats.get()
+    }
+
+    pub fn reset_input_stats(&self) {
+        self.stats.set(InputStats::default());
+    }
+
+    /// Queues a synthetic event, e.g. for scripted demos or tests. The next
+    /// `poll` returns every queued event without reading from the terminal.
+    pub fn inject(&self, event: Event) {
+        self.injected.borrow_mut().push_back(event);
+    }
+
+    /// Queues several synthetic events at once.
+    pub fn inject_all(&self, events: impl IntoIterator<Item = Event>) {
+        self.injected.borrow_mut().extend(events);
+    }
+
+    /// Returns every pending event, waiting up to the poll rate for the first.
+    pub fn poll(&self) -> Result<Vec<Event>> {
+        let mut stats = self.stats.get();
+        let injected: Vec<Event> = self.injected.borrow_mut().drain(..).collect();
+        if !injected.is_empty() {
+            stats.dispatched += injected.len() as u64;
+            self.stats.set(stats);
+            return Ok(injected);
+        }
+
+        let mut events = Vec::new();
+        let mut timeout = self.poll_rate;
+
+        while event::poll(timeout)? {
+            timeout = Duration::from_millis(0);
+            stats.polled += 1;
+            let Some(event) = self.convert(event::read()?) else {
+                stats.dropped += 1;
+                continue;
+            };
+
+            let replaces_last = self.coalesce_moves
+                && matches!(event, Event::MouseMove { .. })
+                && matches!(events.last(), Some(Event::MouseMove { .. }));
+            if replaces_last {
+                events.pop();
+                stats.dropped += 1;
+            }
+            events.push(event);
+        }
+
+        stats.dispatched += events.len() as u64;
+        self.stats.set(stats);
+        Ok(events)
+    }
+
+    fn convert(&self, event: CrosstermEvent) -> Option<Event> {
+        let scroll = self.scroll_lines.min(i8::MAX as u16) as i8;
+        match event {
+            CrosstermEvent::Key(key) if key.kind != KeyEventKind::Release => Some(Event::from_crossterm_key(&key)),
+            CrosstermEvent::Resize(width, height) => Some(Event::Resize { width, height }),
+            CrosstermEvent::Mouse(mouse) => {
+                let (x, y) = (mouse.column, mouse.row);
+                let convert_button = |button| match button {
+                    CrosstermMouseButton::Left => MouseButton::Left,
+                    CrosstermMouseButton::Right => MouseButton::Right,
+                    CrosstermMouseButton::Middle => MouseButton::Middle,
+                };
+                match mouse.kind {
+                    MouseEventKind::Moved => Some(Event::MouseMove { x, y }),
+                    MouseEventKind::Down(button) => Some(Event::MouseClick { x, y, button: convert_button(button) }),
+                    MouseEventKind::Drag(button) => Some(Event::MouseDrag { x, y, button: convert_button(button) }),
+                    MouseEventKind::Up(button) => Some(Event::MouseRelease { x, y, button: convert_button(button) }),
+                    MouseEventKind::ScrollUp => Some(Event::MouseScroll { delta: -scroll }),
+                    MouseEventKind::ScrollDown => Some(Event::MouseScroll { delta: scroll }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for MouseHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MouseHandler {
+    fn drop(&mut self) {
+        let _ = self.disable_capture();
+    }
+}
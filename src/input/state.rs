@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use crate::Event;
+
+/// A key going down or coming back up, as reported by
+/// `KeyboardHandler::poll_transitions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyTransition {
+    Pressed(Event),
+    Released(Event),
+}
+
+/// Tracks which keys are held down, for real-time games that move while a
+/// key is held rather than once per key press.
+///
+/// Feed it key transitions once per frame with `update`. Keys are identified
+/// by their `Event` (e.g. `Event::KeyLeft` or `Event::Character(' ')`).
+/// Modifiers are ignored, so Shift+Left holds `Event::KeyLeft`, and releasing
+/// Shift before Left doesn't leave it stuck down.
+///
+/// Most terminals only report presses. Unless the terminal reports releases
+/// (see `KeyboardHandler::enable_release_events`), a key counts as released
+/// once no press or auto-repeat has arrived for the release timeout. Keep in
+/// mind that key repeat usually starts after a delay of a few hundred
+/// milliseconds, so held keys may briefly appear released in this mode.
+pub struct InputState {
+    down: HashMap<Event, Down>,
+    just_pressed: HashSet<Event>,
+    just_released: HashSet<Event>,
+    release_timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Down {
+    /// When the key went down
+    since: Instant,
+    /// When the key was last pressed or repeated
+    last_seen: Instant,
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self {
+            down: HashMap::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+            release_timeout: Some(Duration::from_millis(200)),
+        }
+    }
+
+    /// Sets how long after its last press a key counts as released, for
+    /// terminals that don't report releases. Pass `None` when release events
+    /// are enabled, so keys stay down until they're actually released.
+    pub fn set_release_timeout(&mut self, timeout: Option<Duration>) {
+        self.release_timeout = timeout;
+    }
+
+    pub fn release_timeout(&self) -> Option<Duration> {
+        self.release_timeout
+    }
+
+    /// Starts a new frame and applies this frame's transitions.
+    pub fn update(&mut self, transitions: &[KeyTransition]) {
+        self.update_at(transitions, Instant::now());
+    }
+
+    /// Like `update`, with an explicit current time.
+    pub fn update_at(&mut self, transitions: &[KeyTransition], now: Instant) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+
+        for transition in transitions {
+            match transition {
+                KeyTransition::Pressed(key) => self.press_at(base_key(key).clone(), now),
+                KeyTransition::Released(key) => self.release(base_key(key)),
+            }
+        }
+
+        if let Some(timeout) = self.release_timeout {
+            let expired: Vec<Event> = self.down.iter()
+                .filter(|(_, down)| now.duration_since(down.last_seen) >= timeout)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in expired {
+                self.release(&key);
+            }
+        }
+    }
+
+    fn press_at(&mut self, key: Event, now: Instant) {
+        match self.down.get_mut(&key) {
+            // A repeat of a key that's already held
+            Some(down) => down.last_seen = now,
+            None => {
+                self.just_pressed.insert(key.clone());
+                self.down.insert(key, Down { since: now, last_seen: now });
+            }
+        }
+    }
+
+    fn release(&mut self, key: &Event) {
+        if self.down.remove(key).is_some() {
+            self.just_released.insert(key.clone());
+        }
+    }
+
+    /// Returns true while the key is held
+    pub fn is_down(&self, key: &Event) -> bool {
+        self.down.contains_key(key)
+    }
+
+    /// Returns true if the key went down this frame
+    pub fn just_pressed(&self, key: &Event) -> bool {
+        self.just_pressed.contains(key)
+    }
+
+    /// Returns true if the key came up this frame
+    pub fn just_released(&self, key: &Event) -> bool {
+        self.just_released.contains(key)
+    }
+
+    /// Returns how long the key has been held, or `None` if it isn't down.
+    ///
+    /// Useful for charge attacks or movement that speeds up the longer a key
+    /// is held.
+    pub fn held_duration(&self, key: &Event) -> Option<Duration> {
+        self.held_duration_at(key, Instant::now())
+    }
+
+    /// Like `held_duration`, with an explicit current time.
+    pub fn held_duration_at(&self, key: &Event, now: Instant) -> Option<Duration> {
+        self.down.get(key).map(|down| now.saturating_duration_since(down.since))
+    }
+
+    /// Iterates over every key currently held
+    pub fn held_keys(&self) -> impl Iterator<Item = &Event> {
+        self.down.keys()
+    }
+
+    /// Releases every key, e.g. when the game loses focus or pauses.
+    pub fn clear(&mut self) {
+        self.down.clear();
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Strips modifiers, since a key's press and release can arrive with
+/// different modifiers held
+fn base_key(key: &Event) -> &Event {
+    match key {
+        Event::KeyWithModifiers { key, .. } => base_key(key),
+        key => key,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Modifiers;
+
+    #[test]
+    fn release_without_modifiers_releases_modified_press() {
+        let mut state = InputState::new();
+        state.set_release_timeout(None);
+        let now = Instant::now();
+
+        let shift_left = Event::KeyLeft.with_modifiers(Modifiers::shift());
+        state.update_at(&[KeyTransition::Pressed(shift_left)], now);
+        assert!(state.is_down(&Event::KeyLeft));
+
+        // Shift came up first, so Left is released without it
+        state.update_at(&[KeyTransition::Released(Event::KeyLeft)], now);
+        assert!(!state.is_down(&Event::KeyLeft));
+        assert!(state.just_released(&Event::KeyLeft));
+        assert_eq!(state.held_keys().count(), 0);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod animation;
+pub mod capabilities;
 pub mod color;
+pub mod dialog;
 pub mod error;
 pub mod event;
 pub mod widgets;
@@ -7,8 +10,13 @@ pub mod macros;
 pub mod game;
 pub mod input;
 pub mod render;
+pub mod screen;
+pub mod style;
+pub mod text;
 
-pub use color::{Color, ColorPair};
+pub use capabilities::{ColorSupport, TerminalCapabilities};
+pub use color::{Color, ColorMode, ColorPair};
 pub use error::{Error, Result};
-pub use event::Event;
-pub use window::{Window, TerminalWindow};
\ No newline at end of file
+pub use event::{Event, Modifiers};
+pub use style::{Style, TextAttributes};
+pub use window::{Window, TerminalWindow, SuspendGuard};
\ No newline at end of file
@@ -1,10 +1,11 @@
 use std::cmp::{min, max};
-use crate::{ColorPair, Result, Error};
+use crate::{ColorPair, Result, Error, TextAttributes};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Cell {
     pub ch: char,
     pub colors: Option<ColorPair>,
+    pub attributes: TextAttributes,
     modified: bool,
 }
 
@@ -14,21 +15,15 @@ pub struct BufferChange {
     pub x: u16,
     pub text: String,
     pub colors: Option<ColorPair>,
+    pub attributes: TextAttributes,
 }
 
 impl Cell {
-    pub fn new(ch: char, colors: Option<ColorPair>) -> Self {
-        Self {
-            ch,
-            colors,
-            modified: true,  // New cells start as modified
-        }
-    }
-
     pub fn empty() -> Self {
         Self {
             ch: ' ',
             colors: None,
+            attributes: TextAttributes::none(),
             modified: false,
         }
     }
@@ -59,15 +54,25 @@ impl Buffer {
         }
     }
 
-    fn size(&self) -> (u16, u16) {
-        (self.width, self.height)
-    }
-
     fn coords_to_index(&self, x: u16, y: u16) -> usize {
         (y as usize * self.width as usize) + x as usize
     }
 
-    pub fn write_char(&mut self, y: u16, x: u16, ch: char, colors: Option<ColorPair>) -> Result<()> {
+    pub fn cell(&self, x: u16, y: u16) -> Option<&Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(&self.current[self.coords_to_index(x, y)])
+    }
+
+    pub fn write_char(
+        &mut self,
+        y: u16,
+        x: u16,
+        ch: char,
+        colors: Option<ColorPair>,
+        attributes: TextAttributes,
+    ) -> Result<()> {
         if x >= self.width || y >= self.height {
             return Err(Error::WindowError("Position out of bounds".into()));
         }
@@ -76,9 +81,10 @@ impl Buffer {
         let cell = &mut self.current[idx];
 
         // Only mark as modified if something changed
-        if cell.ch != ch || cell.colors != colors {
+        if cell.ch != ch || cell.colors != colors || cell.attributes != attributes {
             cell.ch = ch;
             cell.colors = colors;
+            cell.attributes = attributes;
             cell.modified = true;
 
             // Update dirty region
@@ -96,6 +102,17 @@ impl Buffer {
     }
 
     pub fn write_str(&mut self, y: u16, x: u16, s: &str, colors: Option<ColorPair>) -> Result<()> {
+        self.write_str_styled(y, x, s, colors, TextAttributes::none())
+    }
+
+    pub fn write_str_styled(
+        &mut self,
+        y: u16,
+        x: u16,
+        s: &str,
+        colors: Option<ColorPair>,
+        attributes: TextAttributes,
+    ) -> Result<()> {
         if x >= self.width || y >= self.height {
             return Err(Error::WindowError("Position out of bounds".into()));
         }
@@ -105,7 +122,7 @@ impl Buffer {
             if x_pos >= self.width {
                 break;  // Stop at edge of buffer
             }
-            self.write_char(y, x_pos, ch, colors)?;
+            self.write_char(y, x_pos, ch, colors, attributes)?;
         }
 
         Ok(())
@@ -113,7 +130,7 @@ impl Buffer {
 
     pub fn clear(&mut self) {
         for cell in &mut self.current {
-            if cell.ch != ' ' || cell.colors.is_some() {
+            if cell.ch != ' ' || cell.colors.is_some() || !cell.attributes.is_empty() {
                 *cell = Cell::empty();
                 cell.modified = true;
             }
@@ -122,6 +139,29 @@ impl Buffer {
         self.dirty_max_y = Some(self.height - 1);
     }
 
+    /// Resets both the current and previously rendered state to blank cells,
+    /// for use after the terminal itself has been erased.
+    pub fn reset(&mut self) {
+        self.current.fill(Cell::empty());
+        self.previous.fill(Cell::empty());
+        self.dirty_min_y = None;
+        self.dirty_max_y = None;
+    }
+
+    /// Marks every cell as modified, so the next `process_changes` emits the
+    /// whole buffer regardless of what was rendered before.
+    pub fn invalidate(&mut self) {
+        if self.height == 0 {
+            return;
+        }
+
+        for cell in &mut self.current {
+            cell.modified = true;
+        }
+        self.dirty_min_y = Some(0);
+        self.dirty_max_y = Some(self.height - 1);
+    }
+
     pub fn clear_line(&mut self, y: u16) -> Result<()> {
         if y >= self.height {
             return Err(Error::WindowError("Line number out of bounds".into()));
@@ -131,7 +171,7 @@ impl Buffer {
         let end_idx = start_idx + self.width as usize;
 
         for cell in &mut self.current[start_idx..end_idx] {
-            if cell.ch != ' ' || cell.colors.is_some() {
+            if cell.ch != ' ' || cell.colors.is_some() || !cell.attributes.is_empty() {
                 *cell = Cell::empty();
                 cell.modified = true;
             }
@@ -150,6 +190,25 @@ impl Buffer {
         Ok(())
     }
 
+    /// Returns the (x, y) position of every cell the next `process_changes`
+    /// call would emit, without consuming the changes.
+    pub fn pending_cells(&self) -> Vec<(u16, u16)> {
+        let mut cells = Vec::new();
+
+        if let (Some(min_y), Some(max_y)) = (self.dirty_min_y, self.dirty_max_y) {
+            for y in min_y..=max_y {
+                for x in 0..self.width {
+                    let idx = self.coords_to_index(x, y);
+                    if self.current[idx].modified || self.current[idx] != self.previous[idx] {
+                        cells.push((x, y));
+                    }
+                }
+            }
+        }
+
+        cells
+    }
+
     pub fn process_changes(&mut self) -> Vec<BufferChange> {
         let mut changes = Vec::new();
 
@@ -171,7 +230,10 @@ impl Buffer {
                         while x + run_length < self.width {
                             let next_idx = self.coords_to_index(x + run_length, y);
                             let next_cell = &self.current[next_idx];
-                            if next_cell.colors != current.colors || !next_cell.modified {
+                            if next_cell.colors != current.colors
+                                || next_cell.attributes != current.attributes
+                                || !next_cell.modified
+                            {
                                 break;
                             }
                             run_str.push(next_cell.ch);
@@ -183,6 +245,7 @@ impl Buffer {
                             x,
                             text: run_str,
                             colors: current.colors,
+                            attributes: current.attributes,
                         });
 
                         x += run_length;
@@ -193,14 +256,54 @@ impl Buffer {
             }
         }
 
-        // Swap buffers and reset state
-        std::mem::swap(&mut self.current, &mut self.previous);
+        // Reset state and remember what was rendered. The flags are cleared
+        // first so that `previous` compares equal to unchanged cells. This
+        // copies rather than swaps so that `current` keeps reflecting what's
+        // on screen.
         for cell in &mut self.current {
             cell.modified = false;
         }
+        self.previous.clone_from(&self.current);
         self.dirty_min_y = None;
         self.dirty_max_y = None;
 
         changes
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_cells_are_not_reemitted() {
+        let mut buffer = Buffer::new(10, 2);
+        buffer.write_str(0, 0, "ab", None).unwrap();
+        assert_eq!(buffer.process_changes().len(), 1);
+
+        // Rewriting the same content changes nothing
+        buffer.write_str(0, 0, "ab", None).unwrap();
+        assert!(buffer.process_changes().is_empty());
+
+        // A change elsewhere on the row emits only that cell
+        buffer.write_char(0, 5, 'x', None, TextAttributes::none()).unwrap();
+        let changes = buffer.process_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].x, changes[0].text.as_str()), (5, "x"));
+    }
+
+    #[test]
+    fn pending_cells_ignores_identical_rewrites() {
+        let mut buffer = Buffer::new(10, 2);
+        buffer.write_str(1, 2, "hi", None).unwrap();
+        assert_eq!(buffer.pending_cells(), vec![(2, 1), (3, 1)]);
+        buffer.process_changes();
+
+        buffer.write_str(1, 2, "hi", None).unwrap();
+        buffer.write_char(1, 0, ' ', None, TextAttributes::none()).unwrap();
+        assert!(buffer.pending_cells().is_empty());
+
+        buffer.write_char(1, 9, 'x', None, TextAttributes::none()).unwrap();
+        assert_eq!(buffer.pending_cells(), vec![(9, 1)]);
+    }
+}
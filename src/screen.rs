@@ -0,0 +1,92 @@
+//! Navigation between full-screen views (menus, game screens, settings, etc.).
+//!
+//! Each view implements `Screen`, and a `ScreenStack` keeps track of which one
+//! is active. Only the top screen receives input and is drawn; returning
+//! `Transition::Pop` goes back to the previous screen.
+
+use crate::{Event, Result, Window};
+
+/// What the stack should do after a screen handles an event.
+pub enum Transition<S = ()> {
+    /// Stay on the current screen
+    None,
+    /// Open a new screen on top of the current one
+    Push(Box<dyn Screen<S>>),
+    /// Close the current screen and go back to the previous one
+    Pop,
+    /// Swap the current screen for a new one
+    Replace(Box<dyn Screen<S>>),
+    /// Close every screen
+    Quit,
+}
+
+/// A full-screen view. `S` is application state shared between screens.
+pub trait Screen<S = ()> {
+    /// Handle an input event and decide whether to navigate.
+    fn update(&mut self, state: &mut S, event: &Event) -> Transition<S>;
+
+    /// Draw the screen to the window
+    fn draw(&self, state: &S, window: &mut dyn Window) -> Result<()>;
+}
+
+pub struct ScreenStack<S = ()> {
+    screens: Vec<Box<dyn Screen<S>>>,
+}
+
+impl<S> ScreenStack<S> {
+    pub fn new(root: impl Screen<S> + 'static) -> Self {
+        Self {
+            screens: vec![Box::new(root)],
+        }
+    }
+
+    pub fn push(&mut self, screen: impl Screen<S> + 'static) {
+        self.screens.push(Box::new(screen));
+    }
+
+    pub fn pop(&mut self) -> Option<Box<dyn Screen<S>>> {
+        self.screens.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.screens.len()
+    }
+
+    /// Returns true once every screen has been closed, meaning the app should exit.
+    pub fn is_empty(&self) -> bool {
+        self.screens.is_empty()
+    }
+
+    /// Passes an event to the top screen and applies the resulting transition.
+    ///
+    /// Returns false once the stack is empty.
+    pub fn update(&mut self, state: &mut S, event: &Event) -> bool {
+        let transition = match self.screens.last_mut() {
+            Some(screen) => screen.update(state, event),
+            None => return false,
+        };
+
+        match transition {
+            Transition::None => {}
+            Transition::Push(screen) => self.screens.push(screen),
+            Transition::Pop => {
+                self.screens.pop();
+            }
+            Transition::Replace(screen) => {
+                self.screens.pop();
+                self.screens.push(screen);
+            }
+            Transition::Quit => self.screens.clear(),
+        }
+
+        !self.screens.is_empty()
+    }
+
+    /// Draws the top screen, if there is one.
+    pub fn draw(&self, state: &S, window: &mut dyn Window) -> Result<()> {
+        match self.screens.last() {
+            Some(screen) => screen.draw(state, window),
+            None => Ok(()),
+        }
+    }
+}
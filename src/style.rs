@@ -0,0 +1,79 @@
+use crate::ColorPair;
+
+/// Text attributes that can be combined with colors when writing text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextAttributes {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+    pub blink: bool,
+}
+
+impl TextAttributes {
+    pub const fn none() -> Self {
+        Self {
+            bold: false,
+            italic: false,
+            underline: false,
+            reverse: false,
+            blink: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::none()
+    }
+}
+
+/// Colors plus text attributes, used with `Window::write_str_styled`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Style {
+    pub colors: Option<ColorPair>,
+    pub attributes: TextAttributes,
+}
+
+impl Style {
+    pub const fn new() -> Self {
+        Self {
+            colors: None,
+            attributes: TextAttributes::none(),
+        }
+    }
+
+    pub const fn with_colors(mut self, colors: ColorPair) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.attributes.bold = true;
+        self
+    }
+
+    pub const fn italic(mut self) -> Self {
+        self.attributes.italic = true;
+        self
+    }
+
+    pub const fn underline(mut self) -> Self {
+        self.attributes.underline = true;
+        self
+    }
+
+    pub const fn reverse(mut self) -> Self {
+        self.attributes.reverse = true;
+        self
+    }
+
+    pub const fn blink(mut self) -> Self {
+        self.attributes.blink = true;
+        self
+    }
+}
+
+impl From<ColorPair> for Style {
+    fn from(colors: ColorPair) -> Self {
+        Self::new().with_colors(colors)
+    }
+}
@@ -0,0 +1,318 @@
+//! Helpers for measuring and clipping strings by terminal cell width.
+//!
+//! Terminal cells aren't the same as bytes or chars: wide glyphs (CJK, many
+//! emoji) take two cells and combining marks take none. These helpers measure
+//! text the way the terminal will actually draw it.
+
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::widgets::Alignment;
+
+/// The ellipsis glyph used when clipping text.
+pub const ELLIPSIS: char = '…';
+
+/// How tab characters are handled when laying out text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabPolicy {
+    /// Leave tab characters as they are
+    Keep,
+    /// Expand tabs with spaces up to the next multiple of the given width
+    Fixed(u16),
+}
+
+impl Default for TabPolicy {
+    fn default() -> Self {
+        TabPolicy::Fixed(4)
+    }
+}
+
+/// Expands tab characters in `s` according to `policy`.
+///
+/// Tab stops are measured in cells from the start of each line.
+pub fn expand_tabs(s: &str, policy: TabPolicy) -> Cow<'_, str> {
+    if !matches!(policy, TabPolicy::Fixed(_)) || !s.contains('\t') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut expanded = String::with_capacity(s.len());
+    expand_tabs_each(s, policy, |_, ch| expanded.push(ch));
+    Cow::Owned(expanded)
+}
+
+/// Like `expand_tabs`, also returning the byte index in `s` that each byte
+/// of the expanded string came from, for carrying per-byte data (like span
+/// colors) across the expansion.
+pub(crate) fn expand_tabs_indexed(s: &str, policy: TabPolicy) -> (String, Vec<usize>) {
+    let mut expanded = String::with_capacity(s.len());
+    let mut sources = Vec::with_capacity(s.len());
+    expand_tabs_each(s, policy, |source, ch| {
+        expanded.push(ch);
+        sources.extend(std::iter::repeat_n(source, ch.len_utf8()));
+    });
+    (expanded, sources)
+}
+
+/// Calls `emit` with each character of the expanded string and the byte
+/// index of the character in `s` it came from.
+fn expand_tabs_each(s: &str, policy: TabPolicy, mut emit: impl FnMut(usize, char)) {
+    let mut column = 0;
+    for (idx, ch) in s.char_indices() {
+        match (ch, policy) {
+            ('\t', TabPolicy::Fixed(tab_width)) => {
+                let spaces = match tab_width {
+                    0 => 0,
+                    _ => tab_width - column % tab_width,
+                };
+                for _ in 0..spaces {
+                    emit(idx, ' ');
+                }
+                column += spaces;
+            }
+            ('\n', _) => {
+                emit(idx, ch);
+                column = 0;
+            }
+            _ => {
+                emit(idx, ch);
+                column += char_width(ch);
+            }
+        }
+    }
+}
+
+/// Returns the number of terminal cells needed to display `s`.
+pub fn cell_width(s: &str) -> u16 {
+    s.width().min(u16::MAX as usize) as u16
+}
+
+/// Returns the number of terminal cells needed to display `ch`.
+pub fn char_width(ch: char) -> u16 {
+    ch.width().unwrap_or(0) as u16
+}
+
+/// Returns the longest prefix of `s` that fits within `max` cells.
+///
+/// Wide glyphs are never split; if one doesn't fit, it's dropped entirely.
+pub fn clip_to_cells(s: &str, max: u16) -> &str {
+    let mut used = 0;
+    for (idx, ch) in s.char_indices() {
+        used += char_width(ch);
+        if used > max {
+            return &s[..idx];
+        }
+    }
+    s
+}
+
+/// Returns the longest suffix of `s` that fits within `max` cells.
+pub fn clip_start_to_cells(s: &str, max: u16) -> &str {
+    let mut used = 0;
+    for (idx, ch) in s.char_indices().rev() {
+        used += char_width(ch);
+        if used > max {
+            return &s[idx + ch.len_utf8()..];
+        }
+    }
+    s
+}
+
+/// Clips `s` to fit within `max` cells, replacing the end with an ellipsis
+/// if anything had to be removed.
+pub fn clip_to_cells_ellipsis(s: &str, max: u16) -> String {
+    if cell_width(s) <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut clipped = clip_to_cells(s, max - 1).to_string();
+    clipped.push(ELLIPSIS);
+    clipped
+}
+
+/// Clips `s` to fit within `max` cells, replacing the start with an ellipsis
+/// if anything had to be removed.
+pub fn clip_start_to_cells_ellipsis(s: &str, max: u16) -> String {
+    if cell_width(s) <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut clipped = ELLIPSIS.to_string();
+    clipped.push_str(clip_start_to_cells(s, max - 1));
+    clipped
+}
+
+/// Which part of a string is removed when it's clipped to fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncateSide {
+    /// Remove the start, keeping the end visible
+    Start,
+    /// Remove the middle, keeping both ends visible (useful for file paths)
+    Middle,
+    /// Remove the end
+    #[default]
+    End,
+}
+
+/// Clips `s` to fit within `max` cells, replacing the removed part with
+/// `marker` (e.g. `"…"` or `"..."`).
+///
+/// Tabs are expanded with `tab_policy` before measuring. The marker's own cell
+/// width is accounted for; if even the marker doesn't fit, it's clipped too.
+pub fn clip_to_cells_ellipsis_ex(
+    s: &str,
+    max: u16,
+    tab_policy: TabPolicy,
+    side: TruncateSide,
+    marker: &str,
+) -> String {
+    let expanded = expand_tabs(s, tab_policy);
+    let s = expanded.as_ref();
+    if cell_width(s) <= max {
+        return s.to_string();
+    }
+
+    let marker_width = cell_width(marker);
+    if marker_width >= max {
+        return clip_to_cells(marker, max).to_string();
+    }
+
+    let available = max - marker_width;
+    match side {
+        TruncateSide::Start => format!("{}{}", marker, clip_start_to_cells(s, available)),
+        TruncateSide::End => format!("{}{}", clip_to_cells(s, available), marker),
+        TruncateSide::Middle => {
+            // Give the end the extra cell, since that's usually the more
+            // interesting part (a file name, the last path segment)
+            let head = available / 2;
+            let tail = available - head;
+            format!("{}{}{}", clip_to_cells(s, head), marker, clip_start_to_cells(s, tail))
+        }
+    }
+}
+
+/// Clips or pads `s` so it's exactly `target` cells wide, padding on the right.
+///
+/// When `ellipsis` is set, clipped text ends with an ellipsis. Tabs are
+/// expanded with `tab_policy` first.
+pub fn fit_to_cells(s: &str, target: u16, tab_policy: TabPolicy, ellipsis: bool) -> String {
+    fit_to_cells_aligned(s, target, tab_policy, ellipsis, Alignment::Left)
+}
+
+/// Like `fit_to_cells`, but pads according to `align`: on the right for
+/// `Left`, on the left for `Right`, and split on both sides for `Center`
+/// (with any odd cell going on the right).
+///
+/// Like `Label`, right-aligned text that doesn't fit is clipped at the start,
+/// so its end stays visible.
+pub fn fit_to_cells_aligned(
+    s: &str,
+    target: u16,
+    tab_policy: TabPolicy,
+    ellipsis: bool,
+    align: Alignment,
+) -> String {
+    let side = match align {
+        Alignment::Right => TruncateSide::Start,
+        Alignment::Left | Alignment::Center => TruncateSide::End,
+    };
+    let marker = if ellipsis { ELLIPSIS.to_string() } else { String::new() };
+    let clipped = clip_to_cells_ellipsis_ex(s, target, tab_policy, side, &marker);
+
+    // Clipping can leave a cell free when a wide glyph doesn't fit
+    let padding = target.saturating_sub(cell_width(&clipped)) as usize;
+    let (left, right) = match align {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+
+    let mut fitted = String::with_capacity(clipped.len() + padding);
+    fitted.extend(std::iter::repeat_n(' ', left));
+    fitted.push_str(&clipped);
+    fitted.extend(std::iter::repeat_n(' ', right));
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_tabs_indexed_maps_output_to_source() {
+        let (expanded, sources) = expand_tabs_indexed("a\tb", TabPolicy::Fixed(4));
+        assert_eq!(expanded, "a   b");
+        assert_eq!(expanded, expand_tabs("a\tb", TabPolicy::Fixed(4)));
+        assert_eq!(sources, vec![0, 1, 1, 1, 2]);
+    }
+
+    fn truncate(s: &str, max: u16, side: TruncateSide, marker: &str) -> String {
+        let clipped = clip_to_cells_ellipsis_ex(s, max, TabPolicy::default(), side, marker);
+        assert!(cell_width(&clipped) <= max, "{clipped:?} is wider than {max}");
+        clipped
+    }
+
+    #[test]
+    fn truncates_each_side() {
+        assert_eq!(truncate("abcdefghij", 5, TruncateSide::Start, "…"), "…ghij");
+        assert_eq!(truncate("abcdefghij", 5, TruncateSide::End, "…"), "abcd…");
+        assert_eq!(truncate("abcdefghij", 5, TruncateSide::Middle, "…"), "ab…ij");
+        assert_eq!(truncate("abcdefghij", 10, TruncateSide::Middle, "…"), "abcdefghij");
+    }
+
+    #[test]
+    fn middle_truncation_gives_the_end_the_odd_cell() {
+        // 6 cells left around the marker split evenly, 7 put the extra one at the end
+        assert_eq!(truncate("abcdefghij", 7, TruncateSide::Middle, "…"), "abc…hij");
+        assert_eq!(truncate("abcdefghij", 8, TruncateSide::Middle, "…"), "abc…ghij");
+        assert_eq!(truncate("abcdefghij", 8, TruncateSide::Middle, "..."), "ab...hij");
+    }
+
+    #[test]
+    fn widths_narrower_than_the_marker() {
+        assert_eq!(truncate("abcdefghij", 2, TruncateSide::End, "..."), "..");
+        assert_eq!(truncate("abcdefghij", 3, TruncateSide::Start, "..."), "...");
+        assert_eq!(truncate("abcdefghij", 0, TruncateSide::Middle, "…"), "");
+        assert_eq!(truncate("abcdefghij", 1, TruncateSide::End, "…"), "…");
+    }
+
+    #[test]
+    fn wide_glyphs_at_the_cut_are_dropped_whole() {
+        // Only 3 cells are left next to the marker, and each glyph takes 2
+        assert_eq!(truncate("你好世界", 4, TruncateSide::End, "…"), "你…");
+        assert_eq!(truncate("你好世界", 4, TruncateSide::Start, "…"), "…界");
+        assert_eq!(truncate("你好世界", 6, TruncateSide::Middle, "…"), "你…界");
+    }
+
+    #[test]
+    fn fitted_text_is_exactly_the_target_width() {
+        let alignments = [Alignment::Left, Alignment::Center, Alignment::Right];
+        for text in ["ab", "你好", "a你b", "你好世界", "tab\there", ""] {
+            for width in 0..10 {
+                for align in alignments {
+                    for ellipsis in [false, true] {
+                        let fitted = fit_to_cells_aligned(text, width, TabPolicy::default(), ellipsis, align);
+                        assert_eq!(cell_width(&fitted), width, "{text:?} in {width} ({align:?}, {ellipsis}): {fitted:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn padding_follows_the_alignment() {
+        let fit = |align| fit_to_cells_aligned("你好", 7, TabPolicy::default(), false, align);
+        assert_eq!(fit(Alignment::Left), "你好   ");
+        assert_eq!(fit(Alignment::Center), " 你好  ");
+        assert_eq!(fit(Alignment::Right), "   你好");
+
+        // A wide glyph that doesn't fit leaves a cell of padding
+        let clipped = |align| fit_to_cells_aligned("你好世", 5, TabPolicy::default(), false, align);
+        assert_eq!(clipped(Alignment::Left), "你好 ");
+        assert_eq!(clipped(Alignment::Right), " 好世");
+    }
+}
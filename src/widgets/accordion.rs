@@ -0,0 +1,195 @@
+use crate::{ColorPair, Event, Result, Window};
+use crate::text::clip_to_cells;
+use super::{Widget, WindowView};
+
+/// A stack of collapsible sections, each with a header row and content that's
+/// only shown (and only takes up space) while the section is expanded.
+///
+/// Section content is drawn relative to the space below its header, so
+/// widgets added as content should be positioned at (0, 0).
+pub struct Accordion {
+    x: u16,
+    y: u16,
+    width: u16,
+    sections: Vec<Section>,
+    single_open: bool,
+    focused: usize,
+    header_colors: Option<ColorPair>,
+    focused_colors: Option<ColorPair>,
+}
+
+struct Section {
+    title: String,
+    content: Box<dyn Widget>,
+    expanded: bool,
+}
+
+impl Accordion {
+    pub fn new(x: u16, y: u16, width: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            sections: Vec::new(),
+            single_open: false,
+            focused: 0,
+            header_colors: None,
+            focused_colors: None,
+        }
+    }
+
+    /// Adds a collapsed section.
+    pub fn with_section(mut self, title: impl Into<String>, content: impl Widget + 'static) -> Self {
+        self.add_section(title, content);
+        self
+    }
+
+    /// When enabled, expanding a section collapses all the others.
+    pub fn with_single_open(mut self, single_open: bool) -> Self {
+        self.single_open = single_open;
+        self
+    }
+
+    pub fn with_header_colors(mut self, colors: ColorPair) -> Self {
+        self.header_colors = Some(colors);
+        self
+    }
+
+    /// Sets the colors of the focused section's header
+    pub fn with_focused_colors(mut self, colors: ColorPair) -> Self {
+        self.focused_colors = Some(colors);
+        self
+    }
+
+    pub fn add_section(&mut self, title: impl Into<String>, content: impl Widget + 'static) {
+        self.sections.push(Section {
+            title: title.into(),
+            content: Box::new(content),
+            expanded: false,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    pub fn is_expanded(&self, index: usize) -> bool {
+        self.sections.get(index).is_some_and(|section| section.expanded)
+    }
+
+    pub fn set_expanded(&mut self, index: usize, expanded: bool) {
+        if index >= self.sections.len() {
+            return;
+        }
+
+        if expanded && self.single_open {
+            for section in &mut self.sections {
+                section.expanded = false;
+            }
+        }
+        self.sections[index].expanded = expanded;
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.set_expanded(index, !self.is_expanded(index));
+    }
+
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Returns the section whose header is at window position (x, y).
+    pub fn header_at(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.x || x >= self.x + self.width {
+            return None;
+        }
+
+        let mut row = self.y;
+        for (index, section) in self.sections.iter().enumerate() {
+            if y == row {
+                return Some(index);
+            }
+            row += 1 + Self::content_height(section);
+        }
+        None
+    }
+
+    /// Handles Up/Down to move focus between headers, Enter/Space to toggle
+    /// the focused section, and clicks on headers. Returns true if a section
+    /// was expanded or collapsed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::KeyUp => self.focused = self.focused.saturating_sub(1),
+            Event::KeyDown => self.focused = (self.focused + 1).min(self.sections.len().saturating_sub(1)),
+            Event::Enter | Event::Character(' ') if !self.sections.is_empty() => {
+                self.toggle(self.focused);
+                return true;
+            }
+            Event::MouseClick { x, y, .. } => {
+                if let Some(index) = self.header_at(x, y) {
+                    self.focused = index;
+                    self.toggle(index);
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn content_height(section: &Section) -> u16 {
+        if section.expanded {
+            section.content.get_size().1
+        } else {
+            0
+        }
+    }
+}
+
+impl Widget for Accordion {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let mut row = self.y;
+        for (index, section) in self.sections.iter().enumerate() {
+            let marker = if section.expanded { '▾' } else { '▸' };
+            let header = format!("{} {}", marker, section.title);
+            let header = clip_to_cells(&header, self.width);
+
+            let colors = if index == self.focused {
+                self.focused_colors.or(self.header_colors)
+            } else {
+                self.header_colors
+            };
+            window.write_str_maybe_colored(row, self.x, header, colors)?;
+            row += 1;
+
+            let height = Self::content_height(section);
+            if height > 0 {
+                // Indent content under the header's marker
+                let mut view = WindowView::new(window, self.x + 2, row, self.width.saturating_sub(2), height);
+                section.content.draw(&mut view)?;
+                row += height;
+            }
+        }
+        Ok(())
+    }
+
+    /// Only expanded sections add to the height.
+    fn get_size(&self) -> (u16, u16) {
+        let height = self.sections.iter()
+            .map(|section| 1 + Self::content_height(section))
+            .sum();
+        (self.width, height)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.sections.iter().map(|section| section.content.as_ref()).collect()
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{Color, ColorPair, Event, Result, Window};
+use crate::text::char_width;
+use super::{BorderChars, Widget};
+
+/// Supplies the glyph and colors for the cell at (column, row)
+type CellFn<'a> = Box<dyn Fn(u16, u16) -> (char, Option<ColorPair>) + 'a>;
+
+/// A grid of cells for board games like chess, checkers or minesweeper.
+///
+/// Cell contents come from a closure, so the board always reflects the game's
+/// own state. One cell can be selected, moved with the arrow keys and picked
+/// by clicking.
+pub struct Board<'a> {
+    x: u16,
+    y: u16,
+    columns: u16,
+    rows: u16,
+    cell_width: u16,
+    cell_height: u16,
+    grid_lines: bool,
+    style: BorderChars,
+    grid_color: Option<ColorPair>,
+    cells: CellFn<'a>,
+    selected: Option<(u16, u16)>,
+    selection_color: Option<ColorPair>,
+}
+
+impl<'a> Board<'a> {
+    pub fn new(x: u16, y: u16, columns: u16, rows: u16) -> Self {
+        Self {
+            x,
+            y,
+            columns,
+            rows,
+            cell_width: 3,
+            cell_height: 1,
+            grid_lines: true,
+            style: BorderChars::single_line(),
+            grid_color: None,
+            cells: Box::new(|_, _| (' ', None)),
+            selected: None,
+            selection_color: None,
+        }
+    }
+
+    /// Sets the size of each cell, not counting grid lines. The glyph is
+    /// centered within the cell.
+    pub fn with_cell_size(mut self, width: u16, height: u16) -> Self {
+        self.cell_width = width.max(1);
+        self.cell_height = height.max(1);
+        self
+    }
+
+    pub fn with_grid_lines(mut self, grid_lines: bool) -> Self {
+        self.grid_lines = grid_lines;
+        self
+    }
+
+    pub fn with_style(mut self, style: BorderChars) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_grid_color(mut self, color: Color) -> Self {
+        self.grid_color = Some(ColorPair::new(color, Color::Transparent));
+        self
+    }
+
+    /// Sets the closure that returns the glyph and colors for each (column, row).
+    pub fn with_cells(mut self, cells: impl Fn(u16, u16) -> (char, Option<ColorPair>) + 'a) -> Self {
+        self.cells = Box::new(cells);
+        self
+    }
+
+    pub fn with_selected(mut self, cell: Option<(u16, u16)>) -> Self {
+        self.set_selected(cell);
+        self
+    }
+
+    /// Sets the colors of the selected cell. By default the cell's own colors
+    /// are inverted.
+    pub fn with_selection_color(mut self, colors: ColorPair) -> Self {
+        self.selection_color = Some(colors);
+        self
+    }
+
+    pub fn selected(&self) -> Option<(u16, u16)> {
+        self.selected
+    }
+
+    /// Selects a cell, ignoring cells outside the board.
+    pub fn set_selected(&mut self, cell: Option<(u16, u16)>) {
+        self.selected = cell.filter(|&(column, row)| column < self.columns && row < self.rows);
+    }
+
+    /// Moves the selection by (dx, dy), stopping at the edges of the board.
+    /// Selects the top-left cell if nothing is selected yet.
+    pub fn move_selection(&mut self, dx: i32, dy: i32) {
+        if self.columns == 0 || self.rows == 0 {
+            return;
+        }
+
+        self.selected = Some(match self.selected {
+            Some((column, row)) => (
+                (column as i32 + dx).clamp(0, self.columns as i32 - 1) as u16,
+                (row as i32 + dy).clamp(0, self.rows as i32 - 1) as u16,
+            ),
+            None => (0, 0),
+        });
+    }
+
+    /// Handles arrow keys, Enter and mouse clicks.
+    ///
+    /// Arrow keys move the selection. Enter and clicks on a cell select it and
+    /// return its (column, row).
+    pub fn handle_event(&mut self, event: &Event) -> Option<(u16, u16)> {
+        match *event {
+            Event::KeyUp => self.move_selection(0, -1),
+            Event::KeyDown => self.move_selection(0, 1),
+            Event::KeyLeft => self.move_selection(-1, 0),
+            Event::KeyRight => self.move_selection(1, 0),
+            Event::Enter => return self.selected,
+            Event::MouseClick { x, y, .. } => {
+                let cell = self.cell_at(x, y)?;
+                self.selected = Some(cell);
+                return Some(cell);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Returns the (column, row) of the cell at window position (x, y), or
+    /// `None` if the point is outside the board or on a grid line.
+    pub fn cell_at(&self, x: u16, y: u16) -> Option<(u16, u16)> {
+        let column = Self::cell_index(x.checked_sub(self.x)?, self.cell_width, self.grid_lines)?;
+        let row = Self::cell_index(y.checked_sub(self.y)?, self.cell_height, self.grid_lines)?;
+        (column < self.columns && row < self.rows).then_some((column, row))
+    }
+
+    /// Maps an offset from the board's edge to a cell index along one axis.
+    fn cell_index(offset: u16, cell_size: u16, grid_lines: bool) -> Option<u16> {
+        if !grid_lines {
+            return Some(offset / cell_size);
+        }
+
+        let stride = cell_size + 1;
+        (offset % stride != 0).then_some(offset / stride)
+    }
+
+    /// The window position of a cell's top-left corner
+    fn cell_origin(&self, column: u16, row: u16) -> (u16, u16) {
+        let line = self.grid_lines as u16;
+        (
+            self.x + line + column * (self.cell_width + line),
+            self.y + line + row * (self.cell_height + line),
+        )
+    }
+
+    /// Builds one horizontal grid line using the given corner/junction characters.
+    fn grid_row(&self, left: char, junction: char, right: char) -> String {
+        let segment: String = std::iter::repeat_n(self.style.horizontal, self.cell_width as usize).collect();
+        let mut line = String::new();
+        line.push(left);
+        for column in 0..self.columns {
+            if column > 0 {
+                line.push(junction);
+            }
+            line.push_str(&segment);
+        }
+        line.push(right);
+        line
+    }
+
+    fn draw_grid(&self, window: &mut dyn Window) -> Result<()> {
+        let style = &self.style;
+        let (_, height) = self.get_size();
+
+        let top = self.grid_row(style.top_left, style.intersect_top, style.top_right);
+        let middle = self.grid_row(style.intersect_left, style.intersect, style.intersect_right);
+        let bottom = self.grid_row(style.bottom_left, style.intersect_bottom, style.bottom_right);
+
+        let stride = self.cell_height + 1;
+        for offset in 0..height {
+            let line = if offset == 0 {
+                &top
+            } else if offset == height - 1 {
+                &bottom
+            } else if offset % stride == 0 {
+                &middle
+            } else {
+                // Only draw the vertical bars so cell contents aren't overwritten
+                let mut x = self.x;
+                for _ in 0..=self.columns {
+                    window.write_str_maybe_colored(self.y + offset, x, &style.vertical.to_string(), self.grid_color)?;
+                    x += self.cell_width + 1;
+                }
+                continue;
+            };
+            window.write_str_maybe_colored(self.y + offset, self.x, line, self.grid_color)?;
+        }
+        Ok(())
+    }
+
+    fn draw_cell(&self, window: &mut dyn Window, column: u16, row: u16) -> Result<()> {
+        let (glyph, colors) = (self.cells)(column, row);
+        let colors = if self.selected == Some((column, row)) {
+            Some(self.selection_color.unwrap_or_else(|| {
+                colors.unwrap_or(ColorPair::new(Color::Transparent, Color::Transparent)).inverse()
+            }))
+        } else {
+            colors
+        };
+
+        // Fill the whole cell so background colors (e.g. a checkerboard) show,
+        // with the glyph centered on the middle row
+        let glyph_width = char_width(glyph).min(self.cell_width);
+        let pad_left = (self.cell_width - glyph_width) / 2;
+        let pad_right = self.cell_width - glyph_width - pad_left;
+        let blank = " ".repeat(self.cell_width as usize);
+        let center = format!("{}{}{}", " ".repeat(pad_left as usize), glyph, " ".repeat(pad_right as usize));
+
+        let (x, y) = self.cell_origin(column, row);
+        for offset in 0..self.cell_height {
+            let line = if offset == self.cell_height / 2 { &center } else { &blank };
+            window.write_str_maybe_colored(y + offset, x, line, colors)?;
+        }
+        Ok(())
+    }
+}
+
+impl Widget for Board<'_> {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        if self.columns == 0 || self.rows == 0 {
+            return Ok(());
+        }
+
+        if self.grid_lines {
+            self.draw_grid(window)?;
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                self.draw_cell(window, column, row)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        if self.grid_lines {
+            (
+                self.columns * (self.cell_width + 1) + 1,
+                self.rows * (self.cell_height + 1) + 1,
+            )
+        } else {
+            (self.columns * self.cell_width, self.rows * self.cell_height)
+        }
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+}
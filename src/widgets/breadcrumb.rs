@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+use crate::{ColorPair, Event, Result, Window};
+use crate::text::{cell_width, clip_start_to_cells_ellipsis, ELLIPSIS};
+use super::{Widget, WidgetArea};
+
+/// A navigation path like `home > projects > minui`.
+///
+/// When the path doesn't fit in its width, middle segments collapse into `…`,
+/// keeping the first segment and as many trailing segments as fit. Clicking a
+/// visible segment reports its index.
+pub struct Breadcrumb {
+    x: u16,
+    y: u16,
+    width: u16,
+    segments: Vec<String>,
+    separator: String,
+    colors: Option<ColorPair>,
+    current_colors: Option<ColorPair>,
+    separator_colors: Option<ColorPair>,
+}
+
+/// One piece of the rendered breadcrumb
+struct Piece<'a> {
+    /// The segment index, or `None` for separators and the collapsed marker
+    segment: Option<usize>,
+    text: Cow<'a, str>,
+    /// Offset from the breadcrumb's x position
+    offset: u16,
+}
+
+impl Breadcrumb {
+    pub fn new(x: u16, y: u16, width: u16, segments: Vec<impl Into<String>>) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            segments: segments.into_iter().map(Into::into).collect(),
+            separator: " > ".to_string(),
+            colors: None,
+            current_colors: None,
+            separator_colors: None,
+        }
+    }
+
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorPair) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Sets the colors of the last (current) segment
+    pub fn with_current_colors(mut self, colors: ColorPair) -> Self {
+        self.current_colors = Some(colors);
+        self
+    }
+
+    pub fn with_separator_colors(mut self, colors: ColorPair) -> Self {
+        self.separator_colors = Some(colors);
+        self
+    }
+
+    pub fn set_segments(&mut self, segments: Vec<impl Into<String>>) {
+        self.segments = segments.into_iter().map(Into::into).collect();
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Picks which segments to show and lays them out.
+    fn layout(&self) -> Vec<Piece<'_>> {
+        let count = self.segments.len();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let separator_width = cell_width(&self.separator);
+        let width_of = |indices: &[Option<usize>]| -> u16 {
+            let text: u16 = indices.iter()
+                .map(|index| index.map_or(1, |i| cell_width(&self.segments[i])))
+                .sum();
+            text + separator_width * (indices.len() as u16 - 1)
+        };
+
+        // Everything, or the first segment, a collapsed marker and as many
+        // trailing segments as fit
+        let all: Vec<Option<usize>> = (0..count).map(Some).collect();
+        let shown = if width_of(&all) <= self.width {
+            Some(all)
+        } else {
+            (1..count.saturating_sub(1)).rev()
+                .map(|trailing| {
+                    let mut shown = vec![Some(0), None];
+                    shown.extend((count - trailing..count).map(Some));
+                    shown
+                })
+                .find(|shown| width_of(shown) <= self.width)
+        };
+
+        let Some(shown) = shown else {
+            // Not even that fits, so show as much of the current segment as possible
+            let last = clip_start_to_cells_ellipsis(&self.segments[count - 1], self.width);
+            return vec![Piece { segment: Some(count - 1), text: last.into(), offset: 0 }];
+        };
+
+        let mut pieces = Vec::new();
+        let mut offset = 0;
+        for (i, index) in shown.into_iter().enumerate() {
+            if i > 0 {
+                pieces.push(Piece { segment: None, text: self.separator.as_str().into(), offset });
+                offset += separator_width;
+            }
+
+            let text = match index {
+                Some(index) => self.segments[index].as_str().into(),
+                None => ELLIPSIS.to_string().into(),
+            };
+            let piece = Piece { segment: index, text, offset };
+            offset += cell_width(&piece.text);
+            pieces.push(piece);
+        }
+        pieces
+    }
+
+    /// Returns the window area and index of each visible segment, for
+    /// hit-testing.
+    pub fn segment_areas(&self) -> Vec<(usize, WidgetArea)> {
+        self.layout().into_iter()
+            .filter_map(|piece| {
+                let area = WidgetArea::new(self.x + piece.offset, self.y, cell_width(&piece.text), 1);
+                piece.segment.map(|segment| (segment, area))
+            })
+            .collect()
+    }
+
+    /// Returns the index of the segment at window position (x, y), if any.
+    pub fn segment_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.segment_areas().into_iter()
+            .find(|(_, area)| area.contains_point(x, y))
+            .map(|(segment, _)| segment)
+    }
+
+    /// Returns the index of the clicked segment for mouse clicks on one.
+    pub fn handle_event(&self, event: &Event) -> Option<usize> {
+        match *event {
+            Event::MouseClick { x, y, .. } => self.segment_at(x, y),
+            _ => None,
+        }
+    }
+}
+
+impl Widget for Breadcrumb {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let last = self.segments.len().checked_sub(1);
+        for piece in self.layout() {
+            let colors = match piece.segment {
+                None => self.separator_colors.or(self.colors),
+                segment if segment == last => self.current_colors.or(self.colors),
+                Some(_) => self.colors,
+            };
+
+            let x = self.x + piece.offset;
+            window.write_str_maybe_colored(self.y, x, &piece.text, colors)?;
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (self.width, 1)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+}
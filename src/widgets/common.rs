@@ -1,4 +1,5 @@
-use crate::{ColorPair, Result, Window};
+use crate::{Color, ColorPair, Event, Result, Style, Window};
+use crate::text::{cell_width, clip_start_to_cells, clip_to_cells};
 
 #[derive(Debug, Clone, Copy)]
 pub struct BorderChars {
@@ -65,37 +66,117 @@ impl BorderChars {
     }
 }
 
+/// A window adapter that translates and clips writes into a sub-region of another window.
+///
+/// Coordinates passed to a view are in content space: they're offset by
+/// `scroll_x`/`scroll_y` first, and anything that falls before the top-left
+/// of the view (or past its size) is dropped.
 pub struct WindowView<'a> {
     pub window: &'a mut dyn Window,
     pub x_offset: u16,
     pub y_offset: u16,
     pub width: u16,
     pub height: u16,
+    scroll_x: u16,
+    scroll_y: u16,
+    /// An optional (width, height) to clip writes to, from the view's top-left
+    clip: Option<(u16, u16)>,
+}
+
+impl<'a> WindowView<'a> {
+    pub fn new(window: &'a mut dyn Window, x_offset: u16, y_offset: u16, width: u16, height: u16) -> Self {
+        Self {
+            window,
+            x_offset,
+            y_offset,
+            width,
+            height,
+            scroll_x: 0,
+            scroll_y: 0,
+            clip: None,
+        }
+    }
+
+    pub fn with_scroll(mut self, scroll_x: u16, scroll_y: u16) -> Self {
+        self.scroll_x = scroll_x;
+        self.scroll_y = scroll_y;
+        self
+    }
+
+    /// Clips writes to a region smaller than the view, such as a child's
+    /// exact cell. The view's size is unchanged, so layout still sees the
+    /// full area.
+    pub fn with_clip(mut self, width: u16, height: u16) -> Self {
+        self.clip = Some((width, height));
+        self
+    }
+
+    /// The size of the region writes are allowed to touch
+    fn visible_size(&self) -> (u16, u16) {
+        match self.clip {
+            Some((width, height)) => (self.width.min(width), self.height.min(height)),
+            None => (self.width, self.height),
+        }
+    }
+
+    /// Translates a content row into a view row, if it's visible.
+    fn visible_row(&self, y: u16) -> Option<u16> {
+        let row = y.checked_sub(self.scroll_y)?;
+        (row < self.visible_size().1).then_some(row)
+    }
+
+    /// Translates a content-space string write into a view column and the part
+    /// of the string that's visible, dropping any characters that fall outside
+    /// the left and right edges of the view.
+    fn visible_span<'s>(&self, x: u16, s: &'s str) -> Option<(u16, &'s str)> {
+        let (col, s) = match x.checked_sub(self.scroll_x) {
+            Some(col) => (col, s),
+            None => {
+                // Trim by cells, so a wide glyph straddling the edge is dropped
+                // without shifting what follows it
+                let remaining = cell_width(s).checked_sub(self.scroll_x - x).filter(|r| *r > 0)?;
+                let visible = clip_start_to_cells(s, remaining);
+                (remaining - cell_width(visible), visible)
+            }
+        };
+        let width = self.visible_size().0;
+        (col < width).then(|| (col, clip_to_cells(s, width - col)))
+    }
 }
 
 impl<'a> Window for WindowView<'a> {
     fn write_str(&mut self, y: u16, x: u16, s: &str) -> Result<()> {
-        if y < self.height && x < self.width {
-            self.window.write_str(
-                y + self.y_offset,
-                x + self.x_offset,
+        match (self.visible_row(y), self.visible_span(x, s)) {
+            (Some(row), Some((col, s))) => self.window.write_str(
+                row + self.y_offset,
+                col + self.x_offset,
                 s
-            )
-        } else {
-            Ok(()) // Silently skip out-of-bounds writes
+            ),
+            _ => Ok(()), // Silently skip out-of-bounds writes
         }
     }
 
     fn write_str_colored(&mut self, y: u16, x: u16, s: &str, colors: ColorPair) -> Result<()> {
-        if y < self.height && x < self.width {
-            self.window.write_str_colored(
-                y + self.y_offset,
-                x + self.x_offset,
+        match (self.visible_row(y), self.visible_span(x, s)) {
+            (Some(row), Some((col, s))) => self.window.write_str_colored(
+                row + self.y_offset,
+                col + self.x_offset,
                 s,
                 colors
-            )
-        } else {
-            Ok(()) // Silently skip out-of-bounds writes
+            ),
+            _ => Ok(()), // Silently skip out-of-bounds writes
+        }
+    }
+
+    fn write_str_styled(&mut self, y: u16, x: u16, s: &str, style: &Style) -> Result<()> {
+        match (self.visible_row(y), self.visible_span(x, s)) {
+            (Some(row), Some((col, s))) => self.window.write_str_styled(
+                row + self.y_offset,
+                col + self.x_offset,
+                s,
+                style
+            ),
+            _ => Ok(()), // Silently skip out-of-bounds writes
         }
     }
 
@@ -104,42 +185,400 @@ impl<'a> Window for WindowView<'a> {
         (self.width, self.height)
     }
 
+    fn style_at(&self, x: u16, y: u16) -> Option<ColorPair> {
+        let row = self.visible_row(y)?;
+        let col = x.checked_sub(self.scroll_x).filter(|col| *col < self.visible_size().0)?;
+        self.window.style_at(col + self.x_offset, row + self.y_offset)
+    }
+
     fn clear_screen(&mut self) -> Result<()> {
+        let (width, height) = self.visible_size();
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
         // Clear the entire view area by translating to window coordinates
         self.window.clear_area(
             self.y_offset,
             self.x_offset,
-            self.y_offset + self.height - 1,
-            self.x_offset + self.width - 1
+            self.y_offset + height - 1,
+            self.x_offset + width - 1
         )
     }
 
     fn clear_line(&mut self, y: u16) -> Result<()> {
-        if y < self.height {
-            // Clear the specified line by translating to window coordinates
-            self.window.clear_area(
-                self.y_offset + y,
-                self.x_offset,
-                self.y_offset + y,
-                self.x_offset + self.width - 1
-            )
-        } else {
-            Ok(()) // Silently skip out-of-bounds clears
+        let width = self.visible_size().0;
+        match self.visible_row(y) {
+            Some(row) if width > 0 => {
+                // Clear the specified line by translating to window coordinates
+                self.window.clear_area(
+                    self.y_offset + row,
+                    self.x_offset,
+                    self.y_offset + row,
+                    self.x_offset + width - 1
+                )
+            }
+            _ => Ok(()), // Silently skip out-of-bounds clears
         }
     }
 
     fn clear_area(&mut self, y1: u16, x1: u16, y2: u16, x2: u16) -> Result<()> {
-        // Check if entirely out of bounds
-        if x1 >= self.width || x2 >= self.width || y1 >= self.height || y2 >= self.height {
+        let (y1, y2) = (y1.min(y2), y1.max(y2));
+        let (x1, x2) = (x1.min(x2), x1.max(x2));
+
+        // Skip areas that are entirely scrolled out of view
+        if y2 < self.scroll_y || x2 < self.scroll_x {
+            return Ok(());
+        }
+
+        let (width, height) = self.visible_size();
+        let view_y1 = y1.saturating_sub(self.scroll_y);
+        let view_x1 = x1.saturating_sub(self.scroll_x);
+        if view_y1 >= height || view_x1 >= width {
             return Ok(());  // Silently skip out-of-bounds clears
         }
 
         // Translate to window coordinates while clamping to view bounds
-        let parent_x1 = self.x_offset + x1;
-        let parent_x2 = self.x_offset + x2.min(self.width - 1);
-        let parent_y1 = self.y_offset + y1;
-        let parent_y2 = self.y_offset + y2.min(self.height - 1);
+        let parent_x1 = self.x_offset + view_x1;
+        let parent_x2 = self.x_offset + (x2 - self.scroll_x).min(width - 1);
+        let parent_y1 = self.y_offset + view_y1;
+        let parent_y2 = self.y_offset + (y2 - self.scroll_y).min(height - 1);
 
         self.window.clear_area(parent_y1, parent_x1, parent_y2, parent_x2)
     }
-}
\ No newline at end of file
+}
+
+/// A rectangular region of cells, used for layout math and hit-testing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WidgetArea {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl WidgetArea {
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// The first column to the right of the area (exclusive)
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// The first row below the area (exclusive)
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains_point(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Translates a mouse event from window coordinates into coordinates
+    /// relative to the area's top-left corner.
+    ///
+    /// Returns `None` for mouse events outside the area. Events without a
+    /// position (keys, scrolling, resizes) are returned unchanged.
+    pub fn local_coords(&self, event: &Event) -> Option<Event> {
+        match *event {
+            Event::MouseMove { x, y } => {
+                self.contains_point(x, y).then(|| Event::MouseMove { x: x - self.x, y: y - self.y })
+            }
+            Event::MouseClick { x, y, button } => {
+                self.contains_point(x, y).then(|| Event::MouseClick { x: x - self.x, y: y - self.y, button })
+            }
+            Event::MouseDrag { x, y, button } => {
+                self.contains_point(x, y).then(|| Event::MouseDrag { x: x - self.x, y: y - self.y, button })
+            }
+            Event::MouseRelease { x, y, button } => {
+                self.contains_point(x, y).then(|| Event::MouseRelease { x: x - self.x, y: y - self.y, button })
+            }
+            _ => Some(event.clone()),
+        }
+    }
+
+    /// Whether the areas share at least one cell. Empty areas never intersect.
+    pub fn intersects(&self, other: &WidgetArea) -> bool {
+        !self.is_empty() && !other.is_empty() &&
+            self.x < other.right() && other.x < self.right() &&
+            self.y < other.bottom() && other.y < self.bottom()
+    }
+
+    /// Returns the overlapping region of both areas, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &WidgetArea) -> Option<WidgetArea> {
+        if !self.intersects(other) {
+            return None;
+        }
+
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Some(Self {
+            x,
+            y,
+            width: self.right().min(other.right()) - x,
+            height: self.bottom().min(other.bottom()) - y,
+        })
+    }
+
+    /// Returns the smallest area containing both areas.
+    ///
+    /// Empty areas are ignored, so the union of an empty area with another
+    /// area is just the other area.
+    pub fn union(&self, other: &WidgetArea) -> WidgetArea {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+        }
+    }
+
+    /// Shrinks the area by `amount` cells on every side.
+    pub fn inset(&self, amount: u16) -> Self {
+        self.inset_xy(amount, amount)
+    }
+
+    /// Shrinks the area by `horizontal` cells on the left and right and by
+    /// `vertical` cells on the top and bottom.
+    ///
+    /// If the inset is larger than the area, the result has zero width/height
+    /// and is positioned at the area's center.
+    pub fn inset_xy(&self, horizontal: u16, vertical: u16) -> Self {
+        Self {
+            x: self.x.saturating_add(horizontal.min(self.width / 2)),
+            y: self.y.saturating_add(vertical.min(self.height / 2)),
+            width: self.width.saturating_sub(horizontal.saturating_mul(2)),
+            height: self.height.saturating_sub(vertical.saturating_mul(2)),
+        }
+    }
+
+    /// Grows the area by `amount` cells on every side, stopping at 0 on the
+    /// top/left and at `u16::MAX` on the bottom/right.
+    pub fn expand(&self, amount: u16) -> Self {
+        let x = self.x.saturating_sub(amount);
+        let y = self.y.saturating_sub(amount);
+        Self {
+            x,
+            y,
+            width: self.right().saturating_add(amount) - x,
+            height: self.bottom().saturating_add(amount) - y,
+        }
+    }
+
+    /// Restricts the area to lie within `bounds`.
+    ///
+    /// If the two don't overlap, the result is a zero-size area positioned at
+    /// the nearest point of `bounds`.
+    pub fn clamp_to(&self, bounds: &WidgetArea) -> Self {
+        let x = self.x.clamp(bounds.x, bounds.right());
+        let y = self.y.clamp(bounds.y, bounds.bottom());
+        let right = self.right().clamp(x, bounds.right());
+        let bottom = self.bottom().clamp(y, bounds.bottom());
+        Self {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+
+    /// Splits the area into a left and right part, with the left part being
+    /// `at` columns wide (clamped to the area's width).
+    pub fn split_horizontal(&self, at: u16) -> (Self, Self) {
+        let at = at.min(self.width);
+        (
+            Self { width: at, ..*self },
+            Self { x: self.x.saturating_add(at), width: self.width - at, ..*self },
+        )
+    }
+
+    /// Splits the area into a top and bottom part, with the top part being
+    /// `at` rows tall (clamped to the area's height).
+    pub fn split_vertical(&self, at: u16) -> (Self, Self) {
+        let at = at.min(self.height);
+        (
+            Self { height: at, ..*self },
+            Self { y: self.y.saturating_add(at), height: self.height - at, ..*self },
+        )
+    }
+}
+
+/// Returns the area a widget of `widget_size` (width, height) occupies when
+/// centered both horizontally and vertically within `area`.
+///
+/// Widgets larger than the area are clamped to its size.
+pub fn center_widget(area: WidgetArea, widget_size: (u16, u16)) -> WidgetArea {
+    let width = widget_size.0.min(area.width);
+    let height = widget_size.1.min(area.height);
+    WidgetArea {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Draws a single-line outline along the edge of `area` to show that it has
+/// focus. Use `area.expand(1)` to draw it around a widget instead of over its
+/// outermost cells.
+///
+/// An outline that extends past the window is shrunk to fit inside it, so
+/// focus stays visible for widgets at the window's edge.
+pub fn draw_focus_ring(window: &mut dyn Window, area: WidgetArea, color: Color) -> Result<()> {
+    let area = clip_to_window(window, area);
+    if area.is_empty() {
+        return Ok(());
+    }
+
+    let chars = BorderChars::single_line();
+    let colors = Some(ColorPair::new(color, Color::Transparent));
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+
+    let inner_width = area.width.saturating_sub(2);
+    window.write_repeated(area.y, area.x + 1, chars.horizontal, inner_width, colors)?;
+    window.write_repeated(bottom, area.x + 1, chars.horizontal, inner_width, colors)?;
+    for y in area.y + 1..bottom {
+        window.write_repeated(y, area.x, chars.vertical, 1, colors)?;
+        window.write_repeated(y, right, chars.vertical, 1, colors)?;
+    }
+
+    draw_focus_corners(window, area, color)
+}
+
+/// Draws just the four corners of an outline around `area`, a lighter focus
+/// indicator that leaves the edges free.
+pub fn draw_focus_corners(window: &mut dyn Window, area: WidgetArea, color: Color) -> Result<()> {
+    let area = clip_to_window(window, area);
+    if area.is_empty() {
+        return Ok(());
+    }
+
+    let chars = BorderChars::single_line();
+    let colors = Some(ColorPair::new(color, Color::Transparent));
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+
+    window.write_repeated(area.y, area.x, chars.top_left, 1, colors)?;
+    window.write_repeated(area.y, right, chars.top_right, 1, colors)?;
+    window.write_repeated(bottom, area.x, chars.bottom_left, 1, colors)?;
+    window.write_repeated(bottom, right, chars.bottom_right, 1, colors)
+}
+
+fn clip_to_window(window: &dyn Window, area: WidgetArea) -> WidgetArea {
+    let (width, height) = window.get_size();
+    area.clamp_to(&WidgetArea::new(0, 0, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_window::RecordingWindow;
+
+    fn writes_through(view: impl FnOnce(&mut RecordingWindow) -> Result<()>) -> Vec<(u16, u16, String)> {
+        let mut window = RecordingWindow::default();
+        view(&mut window).unwrap();
+        window.writes
+    }
+
+    #[test]
+    fn view_offsets_and_clips_at_the_right_edge() {
+        let writes = writes_through(|window| {
+            let mut view = WindowView::new(window, 10, 5, 4, 2);
+            view.write_str(0, 1, "abcdef")?;
+            view.write_str(1, 4, "hidden")?;
+            view.write_str(2, 0, "below")
+        });
+        assert_eq!(writes, vec![(5, 11, "abc".to_string())]);
+    }
+
+    #[test]
+    fn scrolled_view_trims_the_left_edge() {
+        let writes = writes_through(|window| {
+            let mut view = WindowView::new(window, 2, 0, 10, 3).with_scroll(3, 1);
+            view.write_str(1, 1, "abcdef")?;
+            view.write_str(0, 0, "scrolled out")
+        });
+        assert_eq!(writes, vec![(0, 2, "cdef".to_string())]);
+    }
+
+    #[test]
+    fn scrolled_view_trims_wide_glyphs_by_cells() {
+        let writes = writes_through(|window| {
+            let mut view = WindowView::new(window, 0, 0, 10, 1).with_scroll(1, 0);
+            // "你" covers content columns 0-1, so it straddles the edge and is
+            // dropped; "好" stays at content column 2
+            view.write_str(0, 0, "你好x")?;
+            view.write_str(0, 0, "a")
+        });
+        assert_eq!(writes, vec![(0, 1, "好x".to_string())]);
+    }
+
+    #[test]
+    fn inset_shrinks_every_side() {
+        let area = WidgetArea::new(2, 3, 10, 6);
+        assert_eq!(area.inset(1), WidgetArea::new(3, 4, 8, 4));
+        assert_eq!(area.inset_xy(2, 1), WidgetArea::new(4, 4, 6, 4));
+    }
+
+    #[test]
+    fn inset_larger_than_area_collapses_at_center() {
+        let area = WidgetArea::new(0, 0, 4, 2);
+        assert_eq!(area.inset(5), WidgetArea::new(2, 1, 0, 0));
+    }
+
+    #[test]
+    fn expand_grows_every_side_and_stops_at_origin() {
+        assert_eq!(WidgetArea::new(5, 5, 2, 2).expand(1), WidgetArea::new(4, 4, 4, 4));
+        assert_eq!(WidgetArea::new(1, 0, 2, 2).expand(2), WidgetArea::new(0, 0, 5, 4));
+    }
+
+    #[test]
+    fn clamp_to_trims_to_bounds() {
+        let bounds = WidgetArea::new(0, 0, 10, 5);
+        assert_eq!(WidgetArea::new(8, 3, 5, 5).clamp_to(&bounds), WidgetArea::new(8, 3, 2, 2));
+        assert_eq!(WidgetArea::new(20, 20, 3, 3).clamp_to(&bounds), WidgetArea::new(10, 5, 0, 0));
+    }
+
+    #[test]
+    fn split_divides_area() {
+        let area = WidgetArea::new(1, 1, 10, 4);
+        assert_eq!(
+            area.split_horizontal(3),
+            (WidgetArea::new(1, 1, 3, 4), WidgetArea::new(4, 1, 7, 4))
+        );
+        assert_eq!(
+            area.split_vertical(1),
+            (WidgetArea::new(1, 1, 10, 1), WidgetArea::new(1, 2, 10, 3))
+        );
+        assert_eq!(area.split_horizontal(20).1, WidgetArea::new(11, 1, 0, 4));
+    }
+
+    #[test]
+    fn empty_areas_never_intersect() {
+        let outer = WidgetArea::new(0, 0, 10, 10);
+        let empty = WidgetArea::new(3, 3, 0, 2);
+        assert!(!outer.intersects(&empty));
+        assert!(!empty.intersects(&outer));
+        assert_eq!(outer.intersection(&empty), None);
+
+        let inner = WidgetArea::new(3, 3, 2, 2);
+        assert!(outer.intersects(&inner));
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        // Touching edges don't overlap
+        assert!(!outer.intersects(&WidgetArea::new(10, 0, 5, 5)));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{Result, Window};
+use super::{center_widget, Widget, WidgetArea, WindowView};
+
+/// Constrains a child widget to an aspect ratio or maximum size within an
+/// allotted area, centering it and clipping anything it draws outside.
+///
+/// The child is drawn into a view of the constrained area, so it should
+/// usually be created at (0, 0).
+pub struct ConstrainedBox {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    child: Box<dyn Widget>,
+    aspect_ratio: Option<f32>,
+    max_size: Option<(u16, u16)>,
+}
+
+impl ConstrainedBox {
+    pub fn new(x: u16, y: u16, width: u16, height: u16, child: impl Widget + 'static) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            child: Box::new(child),
+            aspect_ratio: None,
+            max_size: None,
+        }
+    }
+
+    /// Keeps the drawn area at `ratio` columns per row, e.g. 2.0 for a board
+    /// of square cells two columns wide. Non-positive ratios are ignored.
+    pub fn with_aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = (ratio > 0.0 && ratio.is_finite()).then_some(ratio);
+        self
+    }
+
+    pub fn with_max_size(mut self, width: u16, height: u16) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+
+    pub fn child_mut(&mut self) -> &mut (dyn Widget + 'static) {
+        self.child.as_mut()
+    }
+
+    /// The area the child is drawn into: the largest rectangle within the
+    /// allotted area that satisfies the constraints, centered.
+    pub fn inner_area(&self) -> WidgetArea {
+        let (mut width, mut height) = (self.width, self.height);
+        if let Some((max_width, max_height)) = self.max_size {
+            width = width.min(max_width);
+            height = height.min(max_height);
+        }
+
+        if let Some(ratio) = self.aspect_ratio {
+            // Use the full height if the matching width fits, otherwise the
+            // full width
+            let width_for_height = (height as f32 * ratio).round() as u16;
+            if width_for_height <= width {
+                width = width_for_height;
+            } else {
+                height = ((width as f32 / ratio).round() as u16).min(height);
+            }
+        }
+
+        center_widget(WidgetArea::new(self.x, self.y, self.width, self.height), (width, height))
+    }
+}
+
+impl Widget for ConstrainedBox {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let area = self.inner_area();
+        if area.is_empty() {
+            return Ok(());
+        }
+
+        let mut view = WindowView::new(window, area.x, area.y, area.width, area.height);
+        self.child.draw(&mut view)
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    fn children(&self) -> Vec<&dyn Widget> {
+        vec![self.child.as_ref()]
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{Window, Result, ColorPair, Color};
-use super::{BorderChars, Widget, WindowView};
+use super::{center_widget, BorderChars, Widget, WidgetArea, WindowView};
 
 pub struct Container {
     x: u16,
@@ -11,6 +11,22 @@ pub struct Container {
     content: Option<Box<dyn Widget>>,
     padding: u16,
     auto_size: bool,
+    center_content: bool,
+    overflow: Overflow,
+    scroll_x: u16,
+    scroll_y: u16,
+}
+
+/// What a `Container` does with content larger than its content area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Clip the content to the content area, so it never draws over the border
+    #[default]
+    Hidden,
+    /// Let the content draw past the content area (up to the window's edge)
+    Visible,
+    /// Clip the content and show it from a scroll offset
+    Scroll,
 }
 
 impl Container {
@@ -25,6 +41,10 @@ impl Container {
             content: None,
             padding: 1,
             auto_size: true,
+            center_content: false,
+            overflow: Overflow::Hidden,
+            scroll_x: 0,
+            scroll_y: 0,
         }
     }
 
@@ -39,13 +59,20 @@ impl Container {
     }
 
     pub fn with_content(mut self, widget: impl Widget + 'static) -> Self {
-        self.content = Some(Box::new(widget));
-        if self.auto_size {
-            self.adjust_size_to_content();
-        }
+        self.set_content(widget);
         self
     }
 
+    /// Sets the content widget and centers it both horizontally and vertically
+    /// within the container's content area.
+    ///
+    /// The widget's own position is treated as an offset from the centered
+    /// origin, so it should usually be created at (0, 0).
+    pub fn center_child(mut self, widget: impl Widget + 'static) -> Self {
+        self.center_content = true;
+        self.with_content(widget)
+    }
+
     pub fn with_padding(mut self, padding: u16) -> Self {
         self.padding = padding;
         self
@@ -56,11 +83,110 @@ impl Container {
         self
     }
 
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// The scroll offset used with `Overflow::Scroll`, as (x, y)
+    pub fn scroll_offset(&self) -> (u16, u16) {
+        (self.scroll_x, self.scroll_y)
+    }
+
+    /// The largest scroll offset that still shows content, as (x, y)
+    pub fn max_scroll(&self) -> (u16, u16) {
+        let (extent_width, extent_height) = self.content_extent();
+        let (inner_width, inner_height) = self.get_inner_dimensions();
+        (extent_width.saturating_sub(inner_width), extent_height.saturating_sub(inner_height))
+    }
+
+    /// Scrolls to an offset, clamped to the content. Only affects drawing with
+    /// `Overflow::Scroll`.
+    pub fn scroll_to(&mut self, x: u16, y: u16) {
+        let (max_x, max_y) = self.max_scroll();
+        self.scroll_x = x.min(max_x);
+        self.scroll_y = y.min(max_y);
+    }
+
+    /// Scrolls by a number of cells, clamped to the content.
+    pub fn scroll_by(&mut self, dx: i16, dy: i16) {
+        let x = self.scroll_x.saturating_add_signed(dx);
+        let y = self.scroll_y.saturating_add_signed(dy);
+        self.scroll_to(x, y);
+    }
+
+    /// Replaces the content widget (e.g. swapping a loading placeholder for the
+    /// loaded content), returning the previous one.
+    pub fn set_content(&mut self, widget: impl Widget + 'static) -> Option<Box<dyn Widget>> {
+        let previous = self.content.replace(Box::new(widget));
+        if self.auto_size {
+            self.adjust_size_to_content();
+        }
+        previous
+    }
+
+    pub fn content(&self) -> Option<&dyn Widget> {
+        self.content.as_deref()
+    }
+
+    /// Removes and returns the content widget, leaving the container empty.
+    pub fn take_content(&mut self) -> Option<Box<dyn Widget>> {
+        let content = self.content.take();
+        if self.auto_size {
+            self.adjust_size_to_content();
+        }
+        content
+    }
+
+    pub fn clear_content(&mut self) {
+        self.take_content();
+    }
+
+    /// Updates the content widget in place, returning the closure's result,
+    /// or `None` if the container is empty.
+    ///
+    /// With auto-sizing on, the container resizes to fit the content afterwards.
+    pub fn update_content<R>(&mut self, update: impl FnOnce(&mut dyn Widget) -> R) -> Option<R> {
+        let result = update(self.content.as_deref_mut()?);
+        if self.auto_size {
+            self.adjust_size_to_content();
+        }
+        Some(result)
+    }
+
+    /// Describes the container and everything nested in it, one widget per
+    /// line with its type, position and size, for debugging layout issues.
+    ///
+    /// Positions are as each widget reports them, i.e. relative to the area
+    /// its parent draws it into.
+    pub fn debug_dump(&self) -> String {
+        let mut out = String::new();
+        dump_widget(self, 0, &mut out);
+        out
+    }
+
     fn adjust_size_to_content(&mut self) {
-        if let Some(widget) = &self.content {
-            let (content_width, content_height) = widget.get_size();
-            self.width = content_width + (self.padding * 2) + 2;
-            self.height = content_height + (self.padding * 2) + 2;
+        let (content_width, content_height) = match &self.content {
+            Some(widget) => widget.get_size(),
+            None => (0, 0),
+        };
+        self.width = content_width + (self.padding * 2) + 2;
+        self.height = content_height + (self.padding * 2) + 2;
+    }
+
+    /// How far the content reaches from the content area's origin
+    fn content_extent(&self) -> (u16, u16) {
+        match &self.content {
+            Some(widget) => {
+                let (x, y) = widget.get_position();
+                let (width, height) = widget.get_size();
+                (x.saturating_add(width), y.saturating_add(height))
+            }
+            None => (0, 0),
         }
     }
 
@@ -80,7 +206,8 @@ impl Widget for Container {
     ///
     /// This method:
     /// 1. Draws the border with optional color
-    /// 2. Creates a constrained view for the content
+    /// 2. Creates a view for the content, clipped or scrolled according to
+    ///    the container's `Overflow`
     /// 3. Draws the content within the view
     fn draw(&self, window: &mut dyn Window) -> Result<()> {
         // Draw borders...
@@ -96,15 +223,9 @@ impl Widget for Container {
             )?;
 
             // Draw edges...
-            for i in 1..self.width - 1 {
-                window.write_str_colored(self.y, self.x + i, &self.style.horizontal.to_string(), color)?;
-                window.write_str_colored(
-                    self.y + self.height - 1,
-                    self.x + i,
-                    &self.style.horizontal.to_string(),
-                    color
-                )?;
-            }
+            let edge_width = self.width.saturating_sub(2);
+            window.write_repeated(self.y, self.x + 1, self.style.horizontal, edge_width, Some(color))?;
+            window.write_repeated(self.y + self.height - 1, self.x + 1, self.style.horizontal, edge_width, Some(color))?;
 
             for i in 1..self.height - 1 {
                 window.write_str_colored(self.y + i, self.x, &self.style.vertical.to_string(), color)?;
@@ -126,14 +247,9 @@ impl Widget for Container {
             )?;
 
             // Draw edges...
-            for i in 1..self.width - 1 {
-                window.write_str(self.y, self.x + i, &self.style.horizontal.to_string())?;
-                window.write_str(
-                    self.y + self.height - 1,
-                    self.x + i,
-                    &self.style.horizontal.to_string(),
-                )?;
-            }
+            let edge_width = self.width.saturating_sub(2);
+            window.write_repeated(self.y, self.x + 1, self.style.horizontal, edge_width, None)?;
+            window.write_repeated(self.y + self.height - 1, self.x + 1, self.style.horizontal, edge_width, None)?;
 
             for i in 1..self.height - 1 {
                 window.write_str(self.y + i, self.x, &self.style.vertical.to_string())?;
@@ -149,16 +265,34 @@ impl Widget for Container {
         if let Some(widget) = &self.content {
             let (inner_x, inner_y) = self.get_inner_position();
             let (inner_width, inner_height) = self.get_inner_dimensions();
+            let mut area = WidgetArea::new(inner_x, inner_y, inner_width, inner_height);
 
-            let mut view = WindowView {
-                window,
-                x_offset: inner_x,
-                y_offset: inner_y,
-                width: inner_width,
-                height: inner_height,
-            };
+            if self.center_content {
+                area = center_widget(area, widget.get_size());
+            }
 
-            widget.draw(&mut view)?;
+            match self.overflow {
+                Overflow::Hidden => {
+                    let mut view = WindowView::new(window, area.x, area.y, area.width, area.height);
+                    widget.draw(&mut view)?;
+                }
+                Overflow::Visible => {
+                    // Grow the view to fit the content, but not past the window
+                    let (extent_width, extent_height) = self.content_extent();
+                    let (window_width, window_height) = window.get_size();
+                    let width = area.width.max(extent_width).min(window_width.saturating_sub(area.x));
+                    let height = area.height.max(extent_height).min(window_height.saturating_sub(area.y));
+                    let mut view = WindowView::new(window, area.x, area.y, width, height);
+                    widget.draw(&mut view)?;
+                }
+                Overflow::Scroll => {
+                    // Clamp here too, in case the content shrank since the last scroll
+                    let (max_x, max_y) = self.max_scroll();
+                    let mut view = WindowView::new(window, area.x, area.y, area.width, area.height)
+                        .with_scroll(self.scroll_x.min(max_x), self.scroll_y.min(max_y));
+                    widget.draw(&mut view)?;
+                }
+            }
         }
 
         Ok(())
@@ -168,7 +302,29 @@ impl Widget for Container {
         (self.width, self.height)
     }
 
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.content.as_deref().into_iter().collect()
+    }
+
     fn get_position(&self) -> (u16, u16) {
         (self.x, self.y)
     }
-}
\ No newline at end of file
+}
+
+fn dump_widget(widget: &dyn Widget, depth: usize, out: &mut String) {
+    let (x, y) = widget.get_position();
+    let (width, height) = widget.get_size();
+    out.push_str(&format!(
+        "{}{} at ({}, {}) size {}x{}\n",
+        "  ".repeat(depth),
+        widget.type_name(),
+        x,
+        y,
+        width,
+        height
+    ));
+
+    for child in widget.children() {
+        dump_widget(child, depth + 1, out);
+    }
+}
@@ -0,0 +1,258 @@
+use std::cell::Cell;
+use crate::{Color, ColorPair, Event, Result, Window};
+use crate::text::{cell_width, clip_to_cells, fit_to_cells, TabPolicy};
+use super::{center_widget, BorderChars, Widget, WidgetArea};
+
+/// Blank columns between the key column and the description column
+const COLUMN_GAP: u16 = 2;
+
+/// A "?" style help screen listing keybindings in two aligned columns,
+/// drawn centered over whatever window it's given.
+///
+/// Hidden by default. `handle_event` scrolls with the arrow and page keys and
+/// closes the overlay on Esc or `?`.
+pub struct HelpOverlay {
+    title: String,
+    entries: Vec<(String, String)>,
+    visible: bool,
+    scroll: usize,
+    /// Entry rows that fit on the last draw, so scrolling stops at the last
+    /// page. Until the first draw, every entry is assumed to fit.
+    page_rows: Cell<usize>,
+    style: BorderChars,
+    border_color: Option<ColorPair>,
+    key_color: Option<ColorPair>,
+    colors: Option<ColorPair>,
+}
+
+impl HelpOverlay {
+    /// Creates an overlay from `(keys, description)` pairs, e.g. `("q", "Quit")`.
+    pub fn new(entries: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
+        Self {
+            title: "Help".to_string(),
+            entries: entries.into_iter().map(|(keys, description)| (keys.into(), description.into())).collect(),
+            visible: false,
+            scroll: 0,
+            page_rows: Cell::new(usize::MAX),
+            style: BorderChars::single_line(),
+            border_color: None,
+            key_color: None,
+            colors: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_style(mut self, style: BorderChars) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_border_color(mut self, color: Color) -> Self {
+        self.border_color = Some(ColorPair::new(color, Color::Transparent));
+        self
+    }
+
+    pub fn with_key_color(mut self, color: Color) -> Self {
+        self.key_color = Some(ColorPair::new(color, Color::Transparent));
+        self
+    }
+
+    /// Sets the colors of the overlay's body, including the blank cells it
+    /// covers.
+    pub fn with_colors(mut self, colors: ColorPair) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.scroll = 0;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.visible {
+            self.hide();
+        } else {
+            self.show();
+        }
+    }
+
+    /// Handles scrolling and closing while the overlay is visible. Returns true
+    /// if the event was used, so the app can skip its own handling.
+    ///
+    /// Scrolling stops at the last page that fit in the most recent `draw`,
+    /// so draw the overlay at least once after showing it; until then there's
+    /// nothing to scroll.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        // The page may have grown since the scroll was set
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let last = self.max_scroll();
+        match event {
+            Event::Escape | Event::Character('?') => self.hide(),
+            Event::KeyUp => self.scroll = self.scroll.saturating_sub(1),
+            Event::KeyDown => self.scroll = (self.scroll + 1).min(last),
+            Event::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            Event::PageDown => self.scroll = (self.scroll + 10).min(last),
+            Event::Home => self.scroll = 0,
+            Event::End => self.scroll = last,
+            _ => return false,
+        }
+        true
+    }
+
+    /// The furthest scroll that still fills the page drawn last
+    fn max_scroll(&self) -> usize {
+        self.entries.len().saturating_sub(self.page_rows.get())
+    }
+
+    fn key_column_width(&self) -> u16 {
+        self.entries.iter().map(|(keys, _)| cell_width(keys)).max().unwrap_or(0)
+    }
+}
+
+impl Widget for HelpOverlay {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let (window_width, window_height) = window.get_size();
+        let area = center_widget(WidgetArea::new(0, 0, window_width, window_height), self.get_size());
+        if area.width < 3 || area.height < 3 {
+            return Ok(());
+        }
+
+        // Borders, with the title in the top edge
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        let inner_width = area.width - 2;
+        let border = self.border_color;
+        window.write_repeated(area.y, area.x, self.style.top_left, 1, border)?;
+        window.write_repeated(area.y, area.x + 1, self.style.horizontal, inner_width, border)?;
+        window.write_repeated(area.y, right, self.style.top_right, 1, border)?;
+        window.write_repeated(bottom, area.x, self.style.bottom_left, 1, border)?;
+        window.write_repeated(bottom, area.x + 1, self.style.horizontal, inner_width, border)?;
+        window.write_repeated(bottom, right, self.style.bottom_right, 1, border)?;
+        for y in area.y + 1..bottom {
+            window.write_repeated(y, area.x, self.style.vertical, 1, border)?;
+            window.write_repeated(y, right, self.style.vertical, 1, border)?;
+        }
+
+        if !self.title.is_empty() && inner_width > 2 {
+            let title = format!(" {} ", clip_to_cells(&self.title, inner_width - 2));
+            let title_x = area.x + 1 + (inner_width - cell_width(&title)) / 2;
+            window.write_str_maybe_colored(area.y, title_x, &title, border)?;
+        }
+
+        // Entries, scrolled so the last page is never partly empty
+        let rows = (area.height - 2) as usize;
+        self.page_rows.set(rows);
+        let scroll = self.scroll.min(self.max_scroll());
+        let content_width = inner_width.saturating_sub(2);
+        let key_width = self.key_column_width().min(content_width);
+        let description_width = content_width.saturating_sub(key_width + COLUMN_GAP);
+        let blank = " ".repeat(inner_width as usize);
+
+        for row in 0..rows {
+            let y = area.y + 1 + row as u16;
+            window.write_str_maybe_colored(y, area.x + 1, &blank, self.colors)?;
+
+            let Some((keys, description)) = self.entries.get(scroll + row) else {
+                continue;
+            };
+            let keys = fit_to_cells(keys, key_width, TabPolicy::default(), true);
+            window.write_str_maybe_colored(y, area.x + 2, &keys, self.key_color.or(self.colors))?;
+
+            if description_width > 0 {
+                let description = fit_to_cells(description, description_width, TabPolicy::default(), true);
+                window.write_str_maybe_colored(y, area.x + 2 + key_width + COLUMN_GAP, &description, self.colors)?;
+            }
+        }
+
+        // Scroll hints on the right border
+        if scroll > 0 {
+            window.write_str_maybe_colored(area.y + 1, right, "↑", border)?;
+        }
+        if scroll + rows < self.entries.len() {
+            window.write_str_maybe_colored(bottom - 1, right, "↓", border)?;
+        }
+
+        Ok(())
+    }
+
+    /// The overlay's natural size. It's clamped to the window when drawn.
+    fn get_size(&self) -> (u16, u16) {
+        let description_width = self.entries.iter()
+            .map(|(_, description)| cell_width(description))
+            .max()
+            .unwrap_or(0);
+        let content_width = self.key_column_width() + COLUMN_GAP + description_width;
+        let width = (content_width + 4).max(cell_width(&self.title) + 6);
+        let height = self.entries.len().min(u16::MAX as usize - 2) as u16 + 2;
+        (width, height)
+    }
+
+    /// Always (0, 0); the overlay centers itself in the window it's drawn to.
+    fn get_position(&self) -> (u16, u16) {
+        (0, 0)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_window::RecordingWindow;
+
+    #[test]
+    fn scrolling_up_from_the_end_moves_immediately() {
+        let entries: Vec<_> = (0..20).map(|i| (format!("k{i:02}"), "action")).collect();
+        let mut overlay = HelpOverlay::new(entries);
+        overlay.show();
+
+        // 5 entry rows fit between the borders
+        let mut window = RecordingWindow::new(40, 7);
+        overlay.draw(&mut window).unwrap();
+
+        overlay.handle_event(&Event::End);
+        overlay.handle_event(&Event::KeyUp);
+        window.writes.clear();
+        overlay.draw(&mut window).unwrap();
+
+        let first_key = window.writes.iter().find(|(_, _, s)| s.starts_with('k')).map(|(_, _, s)| s.as_str());
+        assert_eq!(first_key, Some("k14"));
+    }
+
+    #[test]
+    fn scrolling_before_the_first_draw_does_nothing() {
+        let entries: Vec<_> = (0..20).map(|i| (format!("k{i:02}"), "action")).collect();
+        let mut overlay = HelpOverlay::new(entries);
+        overlay.show();
+        overlay.handle_event(&Event::End);
+
+        let first_key = |overlay: &HelpOverlay| {
+            let mut window = RecordingWindow::new(40, 7);
+            overlay.draw(&mut window).unwrap();
+            window.writes.into_iter().map(|(_, _, s)| s).find(|s| s.starts_with('k'))
+        };
+        assert_eq!(first_key(&overlay).as_deref(), Some("k00"));
+
+        overlay.handle_event(&Event::End);
+        assert_eq!(first_key(&overlay).as_deref(), Some("k15"));
+    }
+}
@@ -1,5 +1,7 @@
+use std::borrow::Cow;
 use crate::{Window, Result, ColorPair, Color};
-use super::Widget;
+use crate::text::{cell_width, clip_start_to_cells, clip_start_to_cells_ellipsis, clip_to_cells, clip_to_cells_ellipsis};
+use super::{VerticalAlignment, Widget};
 
 pub struct Label {
     x: u16,
@@ -7,6 +9,19 @@ pub struct Label {
     text: String,
     colors: Option<ColorPair>,
     alignment: Alignment,
+    v_alignment: VerticalAlignment,
+    ellipsis: bool,
+    marquee: Option<Marquee>,
+}
+
+/// Number of blank cells between the end of a marquee's text and its next loop
+const MARQUEE_GAP: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+struct Marquee {
+    step_ms: u64,
+    started_at: Option<u64>,
+    offset: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -24,6 +39,9 @@ impl Label {
             text: text.into(),
             colors: None,
             alignment: Alignment::Left,
+            v_alignment: VerticalAlignment::Top,
+            ellipsis: false,
+            marquee: None,
         }
     }
 
@@ -42,6 +60,45 @@ impl Label {
         self
     }
 
+    /// Sets where the label sits vertically within the height of the window
+    /// (or view) it's drawn into, matching `TextBlock`'s vertical alignment.
+    pub fn with_vertical_alignment(mut self, v_alignment: VerticalAlignment) -> Self {
+        self.v_alignment = v_alignment;
+        self
+    }
+
+    /// When enabled, text wider than the available space is clipped with an
+    /// ellipsis instead of running off the edge. Right-aligned labels are
+    /// clipped at the start so their end stays visible.
+    pub fn with_ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Scrolls the text horizontally when it doesn't fit, shifting by one
+    /// cell every `step_ms` milliseconds and looping with a small gap.
+    /// Text that fits is drawn normally.
+    ///
+    /// The animation is advanced by calling `tick` every frame.
+    pub fn with_marquee(mut self, step_ms: u64) -> Self {
+        self.marquee = Some(Marquee {
+            step_ms: step_ms.max(1),
+            started_at: None,
+            offset: 0,
+        });
+        self
+    }
+
+    /// Advances the marquee animation to the given time in milliseconds
+    /// (any monotonic clock works, as long as it's used consistently).
+    pub fn tick(&mut self, now_ms: u64) {
+        if let Some(marquee) = &mut self.marquee {
+            let started_at = *marquee.started_at.get_or_insert(now_ms);
+            let cycle = cell_width(&self.text) as usize + MARQUEE_GAP;
+            marquee.offset = (now_ms.saturating_sub(started_at) / marquee.step_ms) as usize % cycle;
+        }
+    }
+
     pub fn set_text(&mut self, text: impl Into<String>) {
         self.text = text.into();
     }
@@ -51,12 +108,42 @@ impl Label {
     }
 
     pub fn get_length(&self) -> u16 {
-        self.text.len() as u16
+        cell_width(&self.text)
+    }
+
+    fn calculate_aligned_y(&self, available_height: u16) -> u16 {
+        match self.v_alignment {
+            VerticalAlignment::Top => self.y,
+            VerticalAlignment::Middle => self.y + available_height.saturating_sub(1) / 2,
+            VerticalAlignment::Bottom => self.y + available_height.saturating_sub(1),
+        }
     }
 
-    fn calculate_aligned_x(&self, available_width: Option<u16>) -> u16 {
+    fn display_text(&self, available_width: u16) -> Cow<'_, str> {
+        if let Some(marquee) = &self.marquee {
+            if cell_width(&self.text) > available_width {
+                let looped = format!("{}{}{}", self.text, " ".repeat(MARQUEE_GAP), self.text);
+                // Drop `offset` cells from the start, padding with a space if
+                // that cuts a wide glyph in half
+                let remaining = (cell_width(&looped) as usize).saturating_sub(marquee.offset) as u16;
+                let tail = clip_start_to_cells(&looped, remaining);
+                let shifted = format!("{}{}", " ".repeat((remaining - cell_width(tail)) as usize), tail);
+                return Cow::Owned(clip_to_cells(&shifted, available_width).to_string());
+            }
+        }
+
+        if !self.ellipsis || cell_width(&self.text) <= available_width {
+            return Cow::Borrowed(&self.text);
+        }
+
+        match self.alignment {
+            Alignment::Right => Cow::Owned(clip_start_to_cells_ellipsis(&self.text, available_width)),
+            _ => Cow::Owned(clip_to_cells_ellipsis(&self.text, available_width)),
+        }
+    }
+
+    fn calculate_aligned_x(&self, available_width: Option<u16>, text_length: u16) -> u16 {
         if let Some(width) = available_width {
-            let text_length = self.get_length();
             match self.alignment {
                 Alignment::Left => self.x,
                 Alignment::Center => {
@@ -82,22 +169,59 @@ impl Label {
 
 impl Widget for Label {
     fn draw(&self, window: &mut dyn Window) -> Result<()> {
-        // Get window size to calculate available width
-        let (window_width, _) = window.get_size();
-        let x_pos = self.calculate_aligned_x(Some(window_width));
+        // Get window size to calculate available width and height
+        let (window_width, window_height) = window.get_size();
+        let text = self.display_text(window_width.saturating_sub(self.x));
+        let x_pos = self.calculate_aligned_x(Some(window_width), cell_width(&text));
+        let y_pos = self.calculate_aligned_y(window_height.saturating_sub(self.y))
+            .min(window_height.saturating_sub(1));
 
-        match self.colors {
-            Some(colors) => window.write_str_colored(self.y, x_pos, &self.text, colors),
-            None => window.write_str(self.y, x_pos, &self.text),
-        }
+        window.write_str_maybe_colored(y_pos, x_pos, &text, self.colors)
     }
 
     fn get_size(&self) -> (u16, u16) {
         // Width is the text length, height is always 1 for a simple/short label
-        (self.text.chars().count() as u16, 1)
+        (cell_width(&self.text), 1)
     }
 
     fn get_position(&self) -> (u16, u16) {
         (self.x, self.y)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_window::RecordingWindow;
+
+    #[test]
+    fn centers_wide_glyphs_by_cells() {
+        // "你好" is 2 glyphs but 4 cells wide, so it starts at (10 - 4) / 2
+        let mut window = RecordingWindow::new(10, 1);
+        Label::new(0, 0, "你好").with_alignment(Alignment::Center).draw(&mut window).unwrap();
+        assert_eq!(window.writes, vec![(0, 3, "你好".to_string())]);
+    }
+
+    #[test]
+    fn vertical_alignment_accounts_for_the_label_row() {
+        // Rows 4..10 are available below the label's position
+        let mut window = RecordingWindow::new(10, 10);
+        Label::new(0, 4, "hi").with_vertical_alignment(VerticalAlignment::Middle).draw(&mut window).unwrap();
+        Label::new(0, 4, "hi").with_vertical_alignment(VerticalAlignment::Bottom).draw(&mut window).unwrap();
+        assert_eq!(window.writes, vec![(6, 0, "hi".to_string()), (9, 0, "hi".to_string())]);
+    }
+
+    #[test]
+    fn marquee_steps_through_wide_glyphs_by_cells() {
+        let mut label = Label::new(0, 0, "你好世界").with_marquee(100);
+        let mut frames = Vec::new();
+        for now_ms in [0, 100, 200] {
+            label.tick(now_ms);
+            let mut window = RecordingWindow::new(5, 1);
+            label.draw(&mut window).unwrap();
+            frames.extend(window.writes.into_iter().map(|(_, _, text)| text));
+        }
+        assert_eq!(frames, vec!["你好", " 好世", "好世"]);
+        assert!(frames.iter().all(|frame| cell_width(frame) <= 5));
+    }
+}
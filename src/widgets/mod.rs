@@ -1,17 +1,37 @@
+mod accordion;
+mod board;
+mod breadcrumb;
 mod common;
+mod constrained;
 mod container;
+mod help_overlay;
 mod input;
 mod label;
 mod layout;
 mod panel;
+mod progress;
+mod segmented;
+mod stepper;
 mod table;
 mod text_block;
+#[cfg(test)]
+mod test_window;
+mod toggle;
 
-pub use common::{BorderChars, WindowView};
-pub use container::{Container};
+pub use accordion::Accordion;
+pub use board::Board;
+pub use breadcrumb::Breadcrumb;
+pub use common::{center_widget, draw_focus_corners, draw_focus_ring, BorderChars, WidgetArea, WindowView};
+pub use constrained::ConstrainedBox;
+pub use container::{Container, Overflow};
+pub use help_overlay::HelpOverlay;
 pub use label::{Label, Alignment};
-pub use panel::Panel;
+pub use panel::{Panel, PanelContent, PanelHeader};
+pub use progress::{progress_bar, ProgressTracker};
+pub use segmented::SegmentedControl;
+pub use stepper::Stepper;
 pub use text_block::{TextBlock, TextWrapMode, VerticalAlignment}; // Horizontal alignment from label
+pub use toggle::{Toggle, ToggleStyle};
 // pub use input::InputField;
 // pub use layout::{Layout, Direction};
 // pub use table::Table;
@@ -29,6 +49,17 @@ pub trait Widget {
     /// Get the widget's position (x, y)
     fn get_position(&self) -> (u16, u16);
 
+    /// Optional: Child widgets, for walking the widget tree
+    fn children(&self) -> Vec<&dyn Widget> {
+        Vec::new()
+    }
+
+    /// Optional: Name of the widget type, used in debug output
+    fn type_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     /// Optional: Check if widget is visible
     fn is_visible(&self) -> bool {
         true
@@ -1,4 +1,5 @@
 use crate::{Color, ColorPair, Window};
+use crate::text::{cell_width, clip_to_cells_ellipsis};
 use super::{Alignment, BorderChars, TextBlock, Widget, WindowView};
 
 pub struct Panel {
@@ -15,13 +16,30 @@ pub struct Panel {
     header_border_color: Option<ColorPair>,
     body_border_color: Option<ColorPair>,
     padding: u16,
-    alignment: Alignment,  // For the body only; header will always be centered
+    alignment: Alignment,  // For the body only; see `PanelHeader` for the header
     auto_size: bool,
+    header_layout: PanelHeader,
+}
+
+/// Where a `Panel` draws its header text.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum PanelHeader {
+    /// A separate, centered header section with its own border
+    #[default]
+    Section,
+    /// Embedded in the top border (`┌─ Title ──┐`), saving the header rows
+    Inline(Alignment),
+    /// A single bordered box with the header as a centered line inside it,
+    /// above the body, and no separator
+    Compact,
 }
 
 pub enum PanelContent {
     Text(String),
     Block(Box<TextBlock>),
+    /// Any widget, drawn into the body with its position relative to the
+    /// body's content area
+    Widget(Box<dyn Widget>),
 }
 
 impl Panel {
@@ -42,6 +60,7 @@ impl Panel {
            padding: 1,
            alignment: Alignment::Left,
            auto_size: true, // Auto sizes the panel by default
+           header_layout: PanelHeader::Section,
        }
     }
 
@@ -69,6 +88,14 @@ impl Panel {
         self
     }
 
+    pub fn with_body_widget(mut self, widget: impl Widget + 'static) -> Self {
+        self.body_content = PanelContent::Widget(Box::new(widget));
+        if self.auto_size {
+            self.adjust_size();
+        }
+        self
+    }
+
     pub fn with_header_style(mut self, style: BorderChars) -> Self {
         self.header_style = style;
         self
@@ -100,6 +127,27 @@ impl Panel {
         self
     }
 
+    /// Embeds the header in the top border instead of giving it its own
+    /// section. The whole panel then uses the body style and border color.
+    pub fn with_inline_title(mut self, alignment: Alignment) -> Self {
+        self.header_layout = PanelHeader::Inline(alignment);
+        if self.auto_size {
+            self.adjust_size();
+        }
+        self
+    }
+
+    /// Draws the panel as a single bordered box, with the header (if any) as
+    /// a plain line inside it. The whole panel uses the body style and border
+    /// color.
+    pub fn with_compact_header(mut self) -> Self {
+        self.header_layout = PanelHeader::Compact;
+        if self.auto_size {
+            self.adjust_size();
+        }
+        self
+    }
+
     pub fn with_padding(mut self, padding: u16) -> Self {
         self.padding = padding;
         self
@@ -125,19 +173,26 @@ impl Panel {
 
     fn adjust_size(&mut self) {
         // Calculate required width
-        let header_width = self.header_text.len() as u16 + 4;
+        let header_width = match self.header_layout {
+            PanelHeader::Section | PanelHeader::Compact => cell_width(&self.header_text) + 4,
+            // Border cells and spaces around the title
+            PanelHeader::Inline(_) => cell_width(&self.header_text) + 6,
+        };
 
         let body_width = match &self.body_content {
             PanelContent::Text(text) => {
                 let body_lines: Vec<&str> = text.lines().collect();
                 body_lines.iter()
-                    .map(|line| line.len() as u16)
+                    .map(|line| cell_width(line))
                     .max()
                     .unwrap_or(0)
             }
             PanelContent::Block(block) => {
                 block.get_size().0
             }
+            PanelContent::Widget(widget) => {
+                widget.get_position().0 + widget.get_size().0
+            }
         };
 
         let max_body_width = body_width + (self.padding * 2) + 2;
@@ -147,37 +202,41 @@ impl Panel {
         let body_height = match &self.body_content {
             PanelContent::Text(text) => text.lines().count() as u16,
             PanelContent::Block(block) => block.get_size().1,
+            PanelContent::Widget(widget) => widget.get_position().1 + widget.get_size().1,
+        };
+
+        self.height = match self.header_layout {
+            PanelHeader::Section => body_height + 5, // 3 lines for the header + body content + bottom border
+            PanelHeader::Inline(_) | PanelHeader::Compact => body_height + self.header_rows() + 1,
         };
+    }
 
-        self.height = body_height + 5; // 3 lines for the header + body content + bottom border
+    /// Rows above the body: the top border, plus the header and separator
+    /// when the header has its own section, or the header line in compact mode
+    fn header_rows(&self) -> u16 {
+        match self.header_layout {
+            PanelHeader::Section => 3,
+            PanelHeader::Inline(_) => 1,
+            PanelHeader::Compact if self.header_text.is_empty() => 1,
+            PanelHeader::Compact => 2,
+        }
     }
 
     fn get_inner_dimensions(&self) -> (u16, u16) {
         let inner_width = self.width.saturating_sub(2);
-        let inner_height = self.height.saturating_sub(4);
+        let inner_height = self.height.saturating_sub(self.header_rows() + 1);
         (inner_width, inner_height)
     }
-}
 
-impl Widget for Panel {
-    // Draws the complete panel including header, borders, and content.
-    //
-    // The drawing process:
-    // 1. Draws the header section with its borders
-    // 2. Draws the centered header text
-    // 3. Draws the separator between header and body
-    // 4. Draws the body borders
-    // 5. Draws the body content with proper alignment
-    fn draw(&self, window: &mut dyn Window) -> crate::Result<()> {
-        // Draw header section
+    /// Draws the separate header section: top border, centered header text
+    /// and the separator above the body.
+    fn draw_header_section(&self, window: &mut dyn Window) -> crate::Result<()> {
         if let Some(color) = self.header_border_color {
             window.write_str_colored(self.y, self.x, &self.header_style.top_left.to_string(), color)?;
             window.write_str_colored(self.y, self.x + self.width - 1, &self.header_style.top_right.to_string(), color)?;
 
             // Top border
-            for i in 1..self.width - 1 {
-                window.write_str_colored(self.y, self.x + i, &self.header_style.horizontal.to_string(), color)?;
-            }
+            window.write_repeated(self.y, self.x + 1, self.header_style.horizontal, self.width.saturating_sub(2), Some(color))?;
 
             // Header vertical borders
             window.write_str_colored(self.y + 1, self.x, &self.header_style.vertical.to_string(), color)?;
@@ -187,9 +246,7 @@ impl Widget for Panel {
             window.write_str(self.y, self.x + self.width - 1, &self.header_style.top_right.to_string())?;
 
             // Top border
-            for i in 1..self.width - 1 {
-                window.write_str(self.y, self.x + i, &self.header_style.horizontal.to_string())?;
-            }
+            window.write_repeated(self.y, self.x + 1, self.header_style.horizontal, self.width.saturating_sub(2), None)?;
 
             // Header vertical borders
             window.write_str(self.y + 1, self.x, &self.header_style.vertical.to_string())?;
@@ -198,7 +255,7 @@ impl Widget for Panel {
 
         // Draw centered header text
         if !self.header_text.is_empty() {
-            let text_x = self.x + (self.width - self.header_text.len() as u16) / 2;
+            let text_x = self.x + self.width.saturating_sub(cell_width(&self.header_text)) / 2;
             if let Some(colors) = self.header_color {
                 window.write_str_colored(self.y + 1, text_x, &self.header_text, colors)?;
             } else {
@@ -210,20 +267,85 @@ impl Widget for Panel {
         if let Some(color) = self.header_border_color {
             window.write_str_colored(self.y + 2, self.x, &self.header_style.intersect_left.to_string(), color)?;
             window.write_str_colored(self.y + 2, self.x + self.width - 1, &self.header_style.intersect_right.to_string(), color)?;
-            for i in 1..self.width - 1 {
-                window.write_str_colored(self.y + 2, self.x + i, &self.header_style.horizontal.to_string(), color)?;
-            }
+            window.write_repeated(self.y + 2, self.x + 1, self.header_style.horizontal, self.width.saturating_sub(2), Some(color))?;
         } else {
             window.write_str(self.y + 2, self.x, &self.header_style.intersect_left.to_string())?;
             window.write_str(self.y + 2, self.x + self.width - 1, &self.header_style.intersect_right.to_string())?;
-            for i in 1..self.width - 1 {
-                window.write_str(self.y + 2, self.x + i, &self.header_style.horizontal.to_string())?;
-            }
+            window.write_repeated(self.y + 2, self.x + 1, self.header_style.horizontal, self.width.saturating_sub(2), None)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws a plain top border in the body style, for the inline and compact
+    /// layouts.
+    fn draw_top_border(&self, window: &mut dyn Window) -> crate::Result<()> {
+        let style = &self.body_style;
+        let corners = [(self.x, style.top_left), (self.x + self.width - 1, style.top_right)];
+        for (x, corner) in corners {
+            window.write_str_maybe_colored(self.y, x, &corner.to_string(), self.body_border_color)?;
+        }
+        window.write_repeated(self.y, self.x + 1, style.horizontal, self.width.saturating_sub(2), self.body_border_color)
+    }
+
+    /// Draws the top border and, if there's header text, a centered header
+    /// line inside the box.
+    fn draw_compact_header(&self, window: &mut dyn Window) -> crate::Result<()> {
+        self.draw_top_border(window)?;
+        if self.header_text.is_empty() {
+            return Ok(());
+        }
+
+        let vertical = self.body_style.vertical.to_string();
+        for x in [self.x, self.x + self.width - 1] {
+            window.write_str_maybe_colored(self.y + 1, x, &vertical, self.body_border_color)?;
+        }
+
+        let title = clip_to_cells_ellipsis(&self.header_text, self.width.saturating_sub(2));
+        let text_x = self.x + self.width.saturating_sub(cell_width(&title)) / 2;
+        window.write_str_maybe_colored(self.y + 1, text_x, &title, self.header_color)
+    }
+
+    /// Draws the top border with the header text embedded in it.
+    fn draw_inline_header(&self, window: &mut dyn Window, alignment: Alignment) -> crate::Result<()> {
+        self.draw_top_border(window)?;
+        let right = self.x + self.width - 1;
+
+        // Keep at least one border cell on each side of the padded title
+        let max_title = self.width.saturating_sub(6);
+        if self.header_text.is_empty() || max_title == 0 {
+            return Ok(());
+        }
+        let title = format!(" {} ", clip_to_cells_ellipsis(&self.header_text, max_title));
+        let title_width = cell_width(&title);
+        let title_x = match alignment {
+            Alignment::Left => self.x + 2,
+            Alignment::Center => self.x + self.width.saturating_sub(title_width) / 2,
+            Alignment::Right => right - 1 - title_width,
+        };
+        window.write_str_maybe_colored(self.y, title_x, &title, self.header_color)
+    }
+}
+
+impl Widget for Panel {
+    // Draws the complete panel including header, borders, and content.
+    //
+    // The drawing process:
+    // 1. Draws the header, either as its own section (borders, centered text
+    //    and separator), embedded in the top border, or as a line inside a
+    //    single box
+    // 2. Draws the body borders
+    // 3. Draws the body content with proper alignment
+    fn draw(&self, window: &mut dyn Window) -> crate::Result<()> {
+        match self.header_layout {
+            PanelHeader::Section => self.draw_header_section(window)?,
+            PanelHeader::Inline(alignment) => self.draw_inline_header(window, alignment)?,
+            PanelHeader::Compact => self.draw_compact_header(window)?,
         }
 
         // Draw body content
         let (inner_width, inner_height) = self.get_inner_dimensions();
-        let body_start_y = self.y + 3;
+        let body_start_y = self.y + self.header_rows();
 
         // Body vertical borders
         if let Some(color) = self.body_border_color {
@@ -251,8 +373,8 @@ impl Widget for Panel {
 
                     let line_x = match self.alignment {
                         Alignment::Left => content_x,
-                        Alignment::Center => content_x + (content_width - line.len() as u16) / 2,
-                        Alignment::Right => content_x + content_width - line.len() as u16,
+                        Alignment::Center => content_x + content_width.saturating_sub(cell_width(line)) / 2,
+                        Alignment::Right => content_x + content_width.saturating_sub(cell_width(line)),
                     };
 
                     if let Some(colors) = self.body_color {
@@ -263,15 +385,25 @@ impl Widget for Panel {
                 }
             }
             PanelContent::Block(block) => {
-                let mut view = WindowView {
+                let mut view = WindowView::new(
                     window,
-                    x_offset: self.x + 1 + self.padding,
-                    y_offset: body_start_y,
-                    width: inner_width - (self.padding * 2),
-                    height: inner_height,
-                };
+                    self.x + 1 + self.padding,
+                    body_start_y,
+                    inner_width - (self.padding * 2),
+                    inner_height,
+                );
                 block.draw(&mut view)?;
             }
+            PanelContent::Widget(widget) => {
+                let mut view = WindowView::new(
+                    window,
+                    self.x + 1 + self.padding,
+                    body_start_y,
+                    inner_width - (self.padding * 2),
+                    inner_height,
+                );
+                widget.draw(&mut view)?;
+            }
         }
 
         // Bottom border
@@ -283,14 +415,13 @@ impl Widget for Panel {
                 &self.body_style.bottom_right.to_string(),
                 color
             )?;
-            for i in 1..self.width - 1 {
-                window.write_str_colored(
-                    self.y + self.height - 1,
-                    self.x + i,
-                    &self.body_style.horizontal.to_string(),
-                    color
-                )?;
-            }
+            window.write_repeated(
+                self.y + self.height - 1,
+                self.x + 1,
+                self.body_style.horizontal,
+                self.width.saturating_sub(2),
+                Some(color)
+            )?;
         } else {
             window.write_str(self.y + self.height - 1, self.x, &self.body_style.bottom_left.to_string())?;
             window.write_str(
@@ -298,13 +429,13 @@ impl Widget for Panel {
                 self.x + self.width - 1,
                 &self.body_style.bottom_right.to_string()
             )?;
-            for i in 1..self.width - 1 {
-                window.write_str(
-                    self.y + self.height - 1,
-                    self.x + i,
-                    &self.body_style.horizontal.to_string()
-                )?;
-            }
+            window.write_repeated(
+                self.y + self.height - 1,
+                self.x + 1,
+                self.body_style.horizontal,
+                self.width.saturating_sub(2),
+                None
+            )?;
         }
 
         Ok(())
@@ -314,7 +445,38 @@ impl Widget for Panel {
         (self.width, self.height)
     }
 
+    fn children(&self) -> Vec<&dyn Widget> {
+        match &self.body_content {
+            PanelContent::Block(block) => vec![block.as_ref() as &dyn Widget],
+            PanelContent::Widget(widget) => vec![widget.as_ref()],
+            PanelContent::Text(_) => Vec::new(),
+        }
+    }
+
     fn get_position(&self) -> (u16, u16) {
         (self.x, self.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::test_window::RecordingWindow;
+
+    #[test]
+    fn centers_wide_glyphs_by_cells() {
+        let mut window = RecordingWindow::new(20, 10);
+        Panel::new(0, 0, 12, 6)
+            .with_auto_size(false)
+            .with_header("你好")
+            .with_body("你好")
+            .with_alignment(Alignment::Center)
+            .draw(&mut window)
+            .unwrap();
+
+        // Header across the full 12-cell width, body within the 8 cells
+        // inside the border and padding
+        let text_writes: Vec<_> = window.writes.iter().filter(|(_, _, text)| text == "你好").collect();
+        assert_eq!(text_writes, vec![&(1, 4, "你好".to_string()), &(3, 4, "你好".to_string())]);
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+/// Renders a text progress bar like `[#####-----]`, where `width` is the
+/// number of cells between the brackets. `fraction` is clamped to 0.0..=1.0.
+pub fn progress_bar(fraction: f32, width: u16) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * width as f32).round() as usize;
+    let empty = width as usize - filled;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(empty))
+}
+
+/// Tracks the progress of a long-running operation and estimates the time left.
+///
+/// Renders as `[#####-----] 50% • 00:12 elapsed • ~00:12 left`.
+pub struct ProgressTracker {
+    started_at: Instant,
+    completed: u64,
+    total: u64,
+    bar_width: u16,
+}
+
+impl ProgressTracker {
+    /// Starts tracking an operation with `total` units of work.
+    pub fn new(total: u64) -> Self {
+        Self {
+            started_at: Instant::now(),
+            completed: 0,
+            total,
+            bar_width: 10,
+        }
+    }
+
+    pub fn with_bar_width(mut self, width: u16) -> Self {
+        self.bar_width = width;
+        self
+    }
+
+    pub fn set_progress(&mut self, completed: u64, total: u64) {
+        self.completed = completed;
+        self.total = total;
+    }
+
+    pub fn increment(&mut self, amount: u64) {
+        self.completed = self.completed.saturating_add(amount);
+    }
+
+    pub fn completed(&self) -> u64 {
+        self.completed
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.completed as f64 / self.total as f64).min(1.0) as f32
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Estimates the remaining time from the average rate so far.
+    ///
+    /// Returns `None` until some work has been completed.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.completed == 0 || self.total == 0 {
+            return None;
+        }
+
+        let remaining = self.total.saturating_sub(self.completed);
+        let per_unit = self.elapsed().as_secs_f64() / self.completed as f64;
+        Some(Duration::from_secs_f64(per_unit * remaining as f64))
+    }
+
+    pub fn render(&self) -> String {
+        let fraction = self.fraction();
+        let eta = match self.eta() {
+            Some(eta) => format!("~{} left", format_duration(eta)),
+            None => "--:-- left".to_string(),
+        };
+
+        format!(
+            "{} {:.0}% • {} elapsed • {}",
+            progress_bar(fraction, self.bar_width),
+            fraction * 100.0,
+            format_duration(self.elapsed()),
+            eta
+        )
+    }
+}
+
+/// Formats a duration as `mm:ss`, or `h:mm:ss` once it reaches an hour.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
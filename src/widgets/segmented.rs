@@ -0,0 +1,172 @@
+use crate::{Color, ColorPair, Event, Result, Window};
+use crate::text::cell_width;
+use super::{BorderChars, Widget, WidgetArea};
+
+/// A bordered bar of labeled segments where exactly one is active, for small
+/// sets of mutually exclusive options like `Day | Week | Month`.
+///
+/// ```text
+/// ┌─────┬──────┬───────┐
+/// │ Day │ Week │ Month │
+/// └─────┴──────┴───────┘
+/// ```
+pub struct SegmentedControl {
+    x: u16,
+    y: u16,
+    segments: Vec<String>,
+    selected: usize,
+    focused: bool,
+    border_chars: BorderChars,
+    border_color: Option<ColorPair>,
+    colors: Option<ColorPair>,
+    active_colors: ColorPair,
+}
+
+impl SegmentedControl {
+    pub fn new(x: u16, y: u16, segments: Vec<impl Into<String>>) -> Self {
+        Self {
+            x,
+            y,
+            segments: segments.into_iter().map(Into::into).collect(),
+            selected: 0,
+            focused: false,
+            border_chars: BorderChars::single_line(),
+            border_color: None,
+            colors: None,
+            active_colors: ColorPair::new(Color::Black, Color::White),
+        }
+    }
+
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.set_selected(index);
+        self
+    }
+
+    pub fn with_border_chars(mut self, chars: BorderChars) -> Self {
+        self.border_chars = chars;
+        self
+    }
+
+    pub fn with_border_color(mut self, colors: ColorPair) -> Self {
+        self.border_color = Some(colors);
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorPair) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Sets the colors of the active segment
+    pub fn with_active_colors(mut self, colors: ColorPair) -> Self {
+        self.active_colors = colors;
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects a segment. Out of range indices are ignored.
+    pub fn set_selected(&mut self, index: usize) {
+        if index < self.segments.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Focused controls respond to the Left and Right arrow keys.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Returns the window area of each segment's label row, including its padding.
+    pub fn segment_areas(&self) -> Vec<WidgetArea> {
+        let mut x = self.x + 1;
+        self.segments.iter()
+            .map(|segment| {
+                let width = cell_width(segment) + 2;
+                let area = WidgetArea::new(x, self.y + 1, width, 1);
+                x += width + 1;
+                area
+            })
+            .collect()
+    }
+
+    /// Returns the index of the segment at window position (x, y), if any.
+    pub fn segment_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.segment_areas().iter().position(|area| area.contains_point(x, y))
+    }
+
+    /// Switches segments on clicks, or on Left/Right while focused. Returns the
+    /// newly selected index when the selection changed.
+    pub fn handle_event(&mut self, event: &Event) -> Option<usize> {
+        let target = match *event {
+            Event::MouseClick { x, y, .. } => self.segment_at(x, y),
+            Event::KeyLeft if self.focused => self.selected.checked_sub(1),
+            Event::KeyRight if self.focused => Some(self.selected + 1).filter(|&i| i < self.segments.len()),
+            _ => None,
+        }?;
+
+        if target == self.selected {
+            return None;
+        }
+        self.selected = target;
+        Some(target)
+    }
+}
+
+impl Widget for SegmentedControl {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let chars = &self.border_chars;
+        let areas = self.segment_areas();
+        let (width, _) = self.get_size();
+        let right = self.x + width - 1;
+        let bottom = self.y + 2;
+
+        window.write_repeated(self.y, self.x + 1, chars.horizontal, width - 2, self.border_color)?;
+        window.write_repeated(bottom, self.x + 1, chars.horizontal, width - 2, self.border_color)?;
+        for (corner_x, top, middle, bottom_char) in [
+            (self.x, chars.top_left, chars.vertical, chars.bottom_left),
+            (right, chars.top_right, chars.vertical, chars.bottom_right),
+        ] {
+            window.write_str_maybe_colored(self.y, corner_x, &top.to_string(), self.border_color)?;
+            window.write_str_maybe_colored(self.y + 1, corner_x, &middle.to_string(), self.border_color)?;
+            window.write_str_maybe_colored(bottom, corner_x, &bottom_char.to_string(), self.border_color)?;
+        }
+
+        for (i, (segment, area)) in self.segments.iter().zip(&areas).enumerate() {
+            if i > 0 {
+                let divider_x = area.x - 1;
+                window.write_str_maybe_colored(self.y, divider_x, &chars.intersect_top.to_string(), self.border_color)?;
+                window.write_str_maybe_colored(self.y + 1, divider_x, &chars.vertical.to_string(), self.border_color)?;
+                window.write_str_maybe_colored(bottom, divider_x, &chars.intersect_bottom.to_string(), self.border_color)?;
+            }
+
+            let colors = if i == self.selected {
+                let active = if self.focused { self.active_colors.inverse() } else { self.active_colors };
+                Some(active)
+            } else {
+                self.colors
+            };
+            window.write_str_maybe_colored(area.y, area.x, &format!(" {} ", segment), colors)?;
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        let labels: u16 = self.segments.iter().map(|segment| cell_width(segment) + 3).sum();
+        (labels.max(1) + 1, 3)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+}
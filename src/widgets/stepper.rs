@@ -0,0 +1,136 @@
+use crate::{ColorPair, Result, Window};
+use crate::text::cell_width;
+use super::Widget;
+
+/// A horizontal row of step labels for multi-step flows like wizards and
+/// onboarding, e.g. `✓ Account ─ ● Profile ─ ○ Finish`.
+///
+/// Completed steps are checked off and the current step is highlighted. The
+/// app decides what to show for `current()`.
+pub struct Stepper {
+    x: u16,
+    y: u16,
+    steps: Vec<String>,
+    current: usize,
+    separator: String,
+    current_colors: Option<ColorPair>,
+    completed_colors: Option<ColorPair>,
+    pending_colors: Option<ColorPair>,
+}
+
+impl Stepper {
+    pub fn new(x: u16, y: u16, steps: Vec<impl Into<String>>) -> Self {
+        Self {
+            x,
+            y,
+            steps: steps.into_iter().map(Into::into).collect(),
+            current: 0,
+            separator: " ─ ".to_string(),
+            current_colors: None,
+            completed_colors: None,
+            pending_colors: None,
+        }
+    }
+
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn with_current_colors(mut self, colors: ColorPair) -> Self {
+        self.current_colors = Some(colors);
+        self
+    }
+
+    pub fn with_completed_colors(mut self, colors: ColorPair) -> Self {
+        self.completed_colors = Some(colors);
+        self
+    }
+
+    pub fn with_pending_colors(mut self, colors: ColorPair) -> Self {
+        self.pending_colors = Some(colors);
+        self
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn is_first(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 >= self.steps.len()
+    }
+
+    /// Moves to the next step. Returns false if already on the last step.
+    #[allow(clippy::should_implement_trait)] // Pairs with `back`; a stepper isn't an iterator
+    pub fn next(&mut self) -> bool {
+        self.go_to(self.current + 1)
+    }
+
+    /// Moves to the previous step. Returns false if already on the first step.
+    pub fn back(&mut self) -> bool {
+        self.current > 0 && self.go_to(self.current - 1)
+    }
+
+    /// Jumps to `step`. Returns false if there's no such step.
+    pub fn go_to(&mut self, step: usize) -> bool {
+        if step >= self.steps.len() {
+            return false;
+        }
+        self.current = step;
+        true
+    }
+
+    fn step_text(&self, index: usize) -> String {
+        let marker = match index.cmp(&self.current) {
+            std::cmp::Ordering::Less => '✓',
+            std::cmp::Ordering::Equal => '●',
+            std::cmp::Ordering::Greater => '○',
+        };
+        format!("{} {}", marker, self.steps[index])
+    }
+}
+
+impl Widget for Stepper {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let mut x = self.x;
+        for index in 0..self.steps.len() {
+            if index > 0 {
+                let colors = if index <= self.current { self.completed_colors } else { self.pending_colors };
+                window.write_str_maybe_colored(self.y, x, &self.separator, colors)?;
+                x += cell_width(&self.separator);
+            }
+
+            let text = self.step_text(index);
+            let colors = match index.cmp(&self.current) {
+                std::cmp::Ordering::Less => self.completed_colors,
+                std::cmp::Ordering::Equal => self.current_colors,
+                std::cmp::Ordering::Greater => self.pending_colors,
+            };
+            window.write_str_maybe_colored(self.y, x, &text, colors)?;
+            x += cell_width(&text);
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        let steps: u16 = (0..self.steps.len()).map(|index| cell_width(&self.step_text(index))).sum();
+        let separators = cell_width(&self.separator) * self.steps.len().saturating_sub(1) as u16;
+        (steps + separators, 1)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{ColorPair, Result, Window};
+
+/// A window for tests that records every string write as (y, x, text)
+pub(crate) struct RecordingWindow {
+    pub width: u16,
+    pub height: u16,
+    pub writes: Vec<(u16, u16, String)>,
+}
+
+impl RecordingWindow {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height, writes: Vec::new() }
+    }
+}
+
+impl Default for RecordingWindow {
+    fn default() -> Self {
+        Self::new(80, 24)
+    }
+}
+
+impl Window for RecordingWindow {
+    fn write_str(&mut self, y: u16, x: u16, s: &str) -> Result<()> {
+        self.writes.push((y, x, s.to_string()));
+        Ok(())
+    }
+
+    fn write_str_colored(&mut self, y: u16, x: u16, s: &str, _colors: ColorPair) -> Result<()> {
+        self.write_str(y, x, s)
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    fn clear_screen(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clear_line(&mut self, _y: u16) -> Result<()> {
+        Ok(())
+    }
+
+    fn clear_area(&mut self, _y1: u16, _x1: u16, _y2: u16, _x2: u16) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,11 +1,16 @@
+use std::ops::Range;
 use crate::{Window, Result, ColorPair};
+use crate::text::{cell_width, char_width, expand_tabs_indexed, TabPolicy};
 use super::{Alignment, Widget};
 
+/// How `TextBlock` fits text to its width. Every mode starts a new row at
+/// each `\n`.
 #[derive(Debug, Clone, Copy)]
 pub enum TextWrapMode {
     None,           // No wrapping, clip text
-    Wrap,           // Wrap at widget boundary
-    WrapWords,      // Wrap at word boundaries
+    Wrap,           // Wrap at widget boundary (same as BreakAnywhere)
+    WrapWords,      // Wrap at word boundaries, breaking words that don't fit on a line
+    BreakAnywhere,  // Break at the widget boundary, even mid-word (URLs, paths, hashes)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -15,17 +20,24 @@ pub enum VerticalAlignment {
     Bottom,
 }
 
+/// A wrapped row of text, split into runs that share the same colors.
+/// Runs without colors use the block's colors.
+type StyledRow = Vec<(String, Option<ColorPair>)>;
+
 pub struct TextBlock {
     x: u16,
     y: u16,
     width: u16,
     height: u16,
     text: String,
+    spans: Vec<(Range<usize>, ColorPair)>,  // Byte ranges of `text` with their own colors
     colors: Option<ColorPair>,
     wrap_mode: TextWrapMode,
     h_align: Alignment,  // Reuse Label's Alignment enum
     v_align: VerticalAlignment,
     scroll_offset: u16,  // For scrollable text
+    wrap_indicator: Option<char>,
+    tab_policy: TabPolicy,
 }
 
 impl TextBlock {
@@ -36,14 +48,33 @@ impl TextBlock {
             width,
             height,
             text: text.into(),
+            spans: Vec::new(),
             colors: None,
             wrap_mode: TextWrapMode::Wrap,
             h_align: Alignment::Left,
             v_align: VerticalAlignment::Top,
             scroll_offset: 0,
+            wrap_indicator: None,
+            tab_policy: TabPolicy::default(),
         }
     }
 
+    /// Creates a text block from pre-styled spans, e.g. the output of a syntax
+    /// highlighter. Each span keeps its colors through wrapping and clipping.
+    pub fn from_spans(x: u16, y: u16, width: u16, height: u16, spans: Vec<(String, ColorPair)>) -> Self {
+        let mut text = String::new();
+        let mut ranges = Vec::with_capacity(spans.len());
+        for (span, colors) in spans {
+            let start = text.len();
+            text.push_str(&span);
+            ranges.push((start..text.len(), colors));
+        }
+
+        let mut block = Self::new(x, y, width, height, text);
+        block.spans = ranges;
+        block
+    }
+
     pub fn with_colors(mut self, colors: ColorPair) -> Self {
         self.colors = Some(colors);
         self
@@ -60,43 +91,87 @@ impl TextBlock {
         self
     }
 
-    fn get_wrapped_lines(&self) -> Vec<String> {
-        match self.wrap_mode {
-            TextWrapMode::None => self.text.lines().map(String::from).collect(),
-            TextWrapMode::Wrap => {
-                // Simple character-based wrapping
-                self.text
-                    .chars()
-                    .collect::<Vec<_>>()
-                    .chunks(self.width as usize)
-                    .map(|chunk| chunk.iter().collect::<String>())
-                    .collect()
-            }
-            TextWrapMode::WrapWords => {
-                let mut lines = Vec::new();
-                let mut current_line = String::new();
-
-                for word in self.text.split_whitespace() {
-                    if current_line.len() + word.len() + 1 <= self.width as usize {
-                        if !current_line.is_empty() {
-                            current_line.push(' ');
-                        }
-                        current_line.push_str(word);
-                    } else {
-                        if !current_line.is_empty() {
-                            lines.push(current_line);
-                        }
-                        current_line = word.to_string();
-                    }
-                }
+    /// Marks soft-wrapped continuation rows with `indicator` (e.g. `↪`) so they
+    /// can be told apart from real line breaks. The indicator takes up a cell,
+    /// which is excluded from the width available for wrapped text.
+    pub fn with_wrap_indicator(mut self, indicator: char) -> Self {
+        self.wrap_indicator = Some(indicator);
+        self
+    }
 
-                if !current_line.is_empty() {
-                    lines.push(current_line);
+    /// Sets how tabs are expanded before wrapping (4-cell tab stops by default).
+    pub fn with_tab_policy(mut self, tab_policy: TabPolicy) -> Self {
+        self.tab_policy = tab_policy;
+        self
+    }
+
+    fn get_wrapped_lines(&self) -> Vec<StyledRow> {
+        let mut lines = Vec::new();
+        let indicator_width = self.wrap_indicator.map_or(0, char_width);
+        let wrap_width = self.width.saturating_sub(indicator_width);
+
+        let mut line_start = 0;
+        for raw_line in self.text.split_inclusive('\n') {
+            let line = raw_line.trim_end_matches('\n').trim_end_matches('\r');
+            let (line, colors) = self.expand_line(line, line_start);
+            line_start += raw_line.len();
+
+            let rows = match self.wrap_mode {
+                TextWrapMode::None => vec![Range { start: 0, end: line.len() }],
+                TextWrapMode::Wrap | TextWrapMode::BreakAnywhere => wrap_line(&line, wrap_width, false),
+                TextWrapMode::WrapWords => wrap_line(&line, wrap_width, true),
+            };
+
+            for (i, row) in rows.into_iter().enumerate() {
+                let mut runs = StyledRow::new();
+                if let Some(indicator) = self.wrap_indicator.filter(|_| i > 0) {
+                    runs.push((indicator.to_string(), None));
                 }
 
-                lines
+                for (idx, ch) in line[row.clone()].char_indices() {
+                    let color = colors[row.start + idx];
+                    match runs.last_mut() {
+                        Some((run, run_color)) if *run_color == color => run.push(ch),
+                        _ => runs.push((ch.to_string(), color)),
+                    }
+                }
+                lines.push(runs);
             }
         }
+
+        lines
+    }
+
+    /// Expands tabs in a line according to the tab policy, returning the
+    /// expanded line and the span colors for each of its bytes.
+    fn expand_line(&self, line: &str, line_start: usize) -> (String, Vec<Option<ColorPair>>) {
+        let (expanded, sources) = expand_tabs_indexed(line, self.tab_policy);
+        let colors = sources.into_iter()
+            .map(|source| self.span_colors_at(line_start + source))
+            .collect();
+        (expanded, colors)
+    }
+
+    fn span_colors_at(&self, byte: usize) -> Option<ColorPair> {
+        let idx = self.spans.partition_point(|(range, _)| range.end <= byte);
+        self.spans.get(idx)
+            .filter(|(range, _)| range.contains(&byte))
+            .map(|(_, colors)| *colors)
+    }
+
+    /// Updates the block's size after its available area changed (e.g. on
+    /// `Event::Resize`), re-wrapping and clamping the scroll offset so it
+    /// doesn't point past the end of the reflowed text.
+    pub fn on_resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+    }
+
+    /// The largest scroll offset that still fills the block with text
+    pub fn max_scroll_offset(&self) -> u16 {
+        let total_lines = self.get_wrapped_lines().len();
+        total_lines.saturating_sub(self.height as usize).min(u16::MAX as usize) as u16
     }
 
     pub fn scroll_to(&mut self, line: u16) {
@@ -105,7 +180,7 @@ impl TextBlock {
 
     pub fn scroll_by(&mut self, delta: i16) {
         self.scroll_offset = if delta.is_negative() {
-            self.scroll_offset.saturating_sub(delta.abs() as u16)
+            self.scroll_offset.saturating_sub(delta.unsigned_abs())
         } else {
             self.scroll_offset.saturating_add(delta as u16)
         };
@@ -143,7 +218,7 @@ impl Widget for TextBlock {
 
         // Get displayable lines starting from the calculated vertical position
         let start_line = self.scroll_offset as usize;
-        let display_lines: Vec<String> = lines
+        let display_lines: Vec<StyledRow> = lines
             .into_iter()
             .skip(start_line)
             .take(self.height as usize)
@@ -157,21 +232,25 @@ impl Widget for TextBlock {
             }
 
             // Calculate x position for each line relative to the block's position
-            let line_x = match self.h_align {
+            let line_width: u16 = line.iter().map(|(run, _)| cell_width(run)).sum();
+            let mut line_x = match self.h_align {
                 Alignment::Left => block_x,
                 Alignment::Center => {
-                    block_x + (self.width.saturating_sub(line.len() as u16)) / 2
+                    block_x + (self.width.saturating_sub(line_width)) / 2
                 }
                 Alignment::Right => {
-                    block_x + self.width.saturating_sub(line.len() as u16)
+                    block_x + self.width.saturating_sub(line_width)
                 }
             };
 
-            // Draw the line
-            if let Some(colors) = self.colors {
-                window.write_str_colored(line_y, line_x, line, colors)?;
-            } else {
-                window.write_str(line_y, line_x, line)?;
+            // Draw the line, one colored run at a time
+            for (run, run_colors) in line {
+                if let Some(colors) = run_colors.or(self.colors) {
+                    window.write_str_colored(line_y, line_x, run, colors)?;
+                } else {
+                    window.write_str(line_y, line_x, run)?;
+                }
+                line_x += cell_width(run);
             }
         }
 
@@ -185,4 +264,129 @@ impl Widget for TextBlock {
     fn get_position(&self) -> (u16, u16) {
         (self.x, self.y)
     }
-}
\ No newline at end of file
+}
+
+/// Splits a single line (without newlines) into rows that fit within `width`
+/// cells, returning the byte range of each row.
+///
+/// With `words` set, rows break at the last space that fits and the space is
+/// dropped; words longer than a whole row are broken mid-word. Wide glyphs are
+/// never split across rows.
+fn wrap_line(line: &str, width: u16, words: bool) -> Vec<Range<usize>> {
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut rows = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    let mut break_at: Option<usize> = None;
+
+    for (idx, ch) in line.char_indices() {
+        let w = char_width(ch);
+
+        if words && ch == ' ' {
+            if idx == start && !rows.is_empty() {
+                // Drop leading spaces on wrapped rows
+                start = idx + 1;
+            } else if used + w > width {
+                rows.push(start..idx);
+                start = idx + 1;
+                used = 0;
+                break_at = None;
+            } else {
+                break_at = Some(idx);
+                used += w;
+            }
+            continue;
+        }
+
+        if used + w > width {
+            if let Some(space) = break_at.take() {
+                let row_end = start + line[start..space].trim_end_matches(' ').len();
+                if row_end > start {
+                    rows.push(start..row_end);
+                }
+                start = space + 1;
+                used = cell_width(&line[start..idx]);
+            }
+
+            if used + w > width {
+                if idx > start {
+                    rows.push(start..idx);
+                }
+                start = idx;
+                used = 0;
+            }
+
+            if w > width {
+                // A glyph wider than the whole row can never be displayed
+                start = idx + ch.len_utf8();
+                continue;
+            }
+        }
+
+        used += w;
+    }
+
+    if start < line.len() || rows.is_empty() {
+        rows.push(start..line.len());
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(line: &str, width: u16, words: bool) -> Vec<&str> {
+        wrap_line(line, width, words).into_iter().map(|row| &line[row]).collect()
+    }
+
+    fn wrapped_text(block: &TextBlock) -> Vec<String> {
+        block.get_wrapped_lines()
+            .into_iter()
+            .map(|row| row.into_iter().map(|(run, _)| run).collect())
+            .collect()
+    }
+
+    #[test]
+    fn long_words_are_broken() {
+        assert_eq!(rows("abcdefgh", 3, false), vec!["abc", "def", "gh"]);
+        assert_eq!(rows("hi abcdefgh", 4, true), vec!["hi", "abcd", "efgh"]);
+        assert_eq!(rows("one two three", 7, true), vec!["one two", "three"]);
+    }
+
+    #[test]
+    fn wide_glyphs_are_never_split() {
+        // 你 would take cells 2 and 3 of a 3-cell row, so it moves down whole
+        assert_eq!(rows("ab你好", 3, false), vec!["ab", "你", "好"]);
+        assert_eq!(rows("ab你好", 3, true), vec!["ab", "你", "好"]);
+    }
+
+    #[test]
+    fn narrow_widths() {
+        assert!(rows("abc", 0, false).is_empty());
+        assert_eq!(rows("abc", 1, false), vec!["a", "b", "c"]);
+        // A glyph wider than the whole row is dropped rather than overflowing
+        assert_eq!(rows("a你b", 1, true), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn newlines_start_new_rows_in_every_mode() {
+        for mode in [TextWrapMode::None, TextWrapMode::Wrap, TextWrapMode::WrapWords, TextWrapMode::BreakAnywhere] {
+            let block = TextBlock::new(0, 0, 10, 5, "ab\ncd\r\n\nef").with_wrap_mode(mode);
+            assert_eq!(wrapped_text(&block), vec!["ab", "cd", "", "ef"], "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn wrap_matches_break_anywhere() {
+        let text = "hello wonderful world";
+        let wrap = TextBlock::new(0, 0, 6, 5, text).with_wrap_mode(TextWrapMode::Wrap);
+        let anywhere = TextBlock::new(0, 0, 6, 5, text).with_wrap_mode(TextWrapMode::BreakAnywhere);
+        assert_eq!(wrapped_text(&wrap), vec!["hello ", "wonder", "ful wo", "rld"]);
+        assert_eq!(wrapped_text(&wrap), wrapped_text(&anywhere));
+    }
+}
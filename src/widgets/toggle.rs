@@ -0,0 +1,127 @@
+use crate::{Color, ColorPair, Event, Result, Window};
+use crate::text::cell_width;
+use super::Widget;
+
+/// How a `Toggle` draws its switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToggleStyle {
+    /// A sliding knob: `[ ●]` when on, `[● ]` when off
+    Knob,
+    /// Text: `[ ON]` when on, `[OFF]` when off
+    Text,
+}
+
+/// An on/off switch for settings, with an optional label drawn after it.
+pub struct Toggle {
+    x: u16,
+    y: u16,
+    value: bool,
+    label: String,
+    style: ToggleStyle,
+    focused: bool,
+    on_colors: ColorPair,
+    off_colors: ColorPair,
+}
+
+impl Toggle {
+    pub fn new(x: u16, y: u16, value: bool) -> Self {
+        Self {
+            x,
+            y,
+            value,
+            label: String::new(),
+            style: ToggleStyle::Knob,
+            focused: false,
+            on_colors: ColorPair::new(Color::Green, Color::Transparent),
+            off_colors: ColorPair::new(Color::White, Color::Transparent),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_style(mut self, style: ToggleStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_on_colors(mut self, colors: ColorPair) -> Self {
+        self.on_colors = colors;
+        self
+    }
+
+    pub fn with_off_colors(mut self, colors: ColorPair) -> Self {
+        self.off_colors = colors;
+        self
+    }
+
+    pub fn value(&self) -> bool {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: bool) {
+        self.value = value;
+    }
+
+    pub fn toggle(&mut self) {
+        self.value = !self.value;
+    }
+
+    /// Focused toggles respond to Space and Enter, and draw their switch inverted.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Flips the toggle on a click anywhere on it, or on Space/Enter while
+    /// focused. Returns true if the value changed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let hit = match *event {
+            Event::MouseClick { x, y, .. } => self.contains_point(x, y),
+            Event::Character(' ') | Event::Enter => self.focused,
+            _ => false,
+        };
+        if hit {
+            self.toggle();
+        }
+        hit
+    }
+
+    fn switch_text(&self) -> &'static str {
+        match (self.style, self.value) {
+            (ToggleStyle::Knob, true) => "[ ●]",
+            (ToggleStyle::Knob, false) => "[● ]",
+            (ToggleStyle::Text, true) => "[ ON]",
+            (ToggleStyle::Text, false) => "[OFF]",
+        }
+    }
+}
+
+impl Widget for Toggle {
+    fn draw(&self, window: &mut dyn Window) -> Result<()> {
+        let switch = self.switch_text();
+        let colors = if self.value { self.on_colors } else { self.off_colors };
+        let colors = if self.focused { colors.inverse() } else { colors };
+        window.write_str_colored(self.y, self.x, switch, colors)?;
+
+        if !self.label.is_empty() {
+            window.write_str(self.y, self.x + cell_width(switch) + 1, &self.label)?;
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> (u16, u16) {
+        let switch = cell_width(self.switch_text());
+        let label = if self.label.is_empty() { 0 } else { cell_width(&self.label) + 1 };
+        (switch + label, 1)
+    }
+
+    fn get_position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+}
@@ -1,23 +1,90 @@
 use std::io::{Write, stdout};
+use std::process::{Command, ExitStatus, Stdio};
 use crossterm::{
     terminal::{self, enable_raw_mode, disable_raw_mode},
     cursor,
-    event::{self, Event as CrosstermEvent, KeyCode},
-    style::{self},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent,
+        KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    style::{self, Attribute},
     execute,
 };
-use crate::{Error, Result, Event, ColorPair};
-use crate::render::buffer::Buffer;
+use crate::{Error, Result, Event, Color, ColorMode, ColorPair, ColorSupport, Style, TerminalCapabilities, TextAttributes};
+use crate::input::modes;
+use crate::render::buffer::{Buffer, Cell};
 
 pub trait Window {
     fn write_str(&mut self, y: u16, x: u16, s: &str) -> Result<()>;
 
     fn write_str_colored(&mut self, y: u16, x: u16, s: &str, colors: ColorPair) -> Result<()>;
 
+    /// Writes text with `colors`, or with the terminal's default colors when
+    /// `colors` is `None`.
+    fn write_str_maybe_colored(&mut self, y: u16, x: u16, s: &str, colors: Option<ColorPair>) -> Result<()> {
+        match colors {
+            Some(colors) => self.write_str_colored(y, x, s, colors),
+            None => self.write_str(y, x, s),
+        }
+    }
+
+    /// Writes text with colors and text attributes (bold, underline, etc.).
+    ///
+    /// Windows that can't render attributes fall back to writing with just
+    /// the style's colors.
+    fn write_str_styled(&mut self, y: u16, x: u16, s: &str, style: &Style) -> Result<()> {
+        self.write_str_maybe_colored(y, x, s, style.colors)
+    }
+
+    /// Writes `count` copies of `ch` starting at (y, x), e.g. for border lines,
+    /// fills and separators. Uses the terminal's default colors when `colors`
+    /// is `None`.
+    fn write_repeated(&mut self, y: u16, x: u16, ch: char, count: u16, colors: Option<ColorPair>) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        let run: String = std::iter::repeat_n(ch, count as usize).collect();
+        self.write_str_maybe_colored(y, x, &run, colors)
+    }
+
     fn get_size(&self) -> (u16, u16);
 
+    /// Returns the colors of the cell at (x, y), or `None` if the position is
+    /// out of bounds or the window can't read its cells back.
+    ///
+    /// `TerminalWindow` answers from its render buffer (what will be on
+    /// screen after the next flush), not from the physical terminal. Cells
+    /// without colors are reported as `Transparent` on `Transparent`.
+    fn style_at(&self, _x: u16, _y: u16) -> Option<ColorPair> {
+        None
+    }
+
     fn clear_screen(&mut self) -> Result<()>;
 
+    /// Resets the whole window to blank, uncolored cells.
+    ///
+    /// For a `TerminalWindow` the terminal is erased immediately; other windows
+    /// apply it the same way as `clear_screen`.
+    fn clear(&mut self) -> Result<()> {
+        self.clear_screen()
+    }
+
+    /// Clears the whole window and fills it with the given background color.
+    ///
+    /// This is a regular buffered write, so it shows up on the next flush
+    /// (or immediately when auto-flush is enabled).
+    fn clear_with(&mut self, color: Color) -> Result<()> {
+        let (width, height) = self.get_size();
+        let blank = " ".repeat(width as usize);
+        let colors = ColorPair::new(Color::Transparent, color);
+        for y in 0..height {
+            self.write_str_colored(y, 0, &blank, colors)?;
+        }
+        Ok(())
+    }
+
     fn clear_line(&mut self, y: u16) -> Result<()>;
 
     fn clear_area(&mut self, y1: u16, x1: u16, y2: u16, x2: u16) -> Result<()>;
@@ -28,16 +95,31 @@ pub struct TerminalWindow {
     height: u16,
     buffer: Buffer,
     auto_flush: bool,
+    writer: Box<dyn Write>,
+    color_mode: ColorMode,
+    colors_enabled: bool,
+    capabilities: TerminalCapabilities,
 }
 
 impl TerminalWindow {
     pub fn new() -> Result<Self> {
+        Self::with_writer(stdout())
+    }
+
+    /// Creates a window that emits its output to `writer` instead of stdout,
+    /// e.g. stderr (to keep stdout free for piping data) or a tee that records
+    /// the session.
+    ///
+    /// Raw mode and the window size still come from the controlling terminal.
+    pub fn with_writer(writer: impl Write + 'static) -> Result<Self> {
+        let mut writer: Box<dyn Write> = Box::new(writer);
         enable_raw_mode()?;
 
         let (cols, rows) = terminal::size()?;
+        let capabilities = TerminalCapabilities::detect();
 
         execute!(
-            stdout(),
+            writer,
             terminal::EnterAlternateScreen,  // Use separate screen buffer
             terminal::Clear(terminal::ClearType::All),
             cursor::Hide,
@@ -49,48 +131,246 @@ impl TerminalWindow {
             height: rows,
             buffer: Buffer::new(cols, rows),
             auto_flush: true,
+            writer,
+            color_mode: ColorMode::Auto,
+            colors_enabled: ColorMode::Auto.colors_enabled_for(&capabilities),
+            capabilities,
         })
     }
 
-    pub fn clear(&self) -> Result<()> {
+    /// A window that doesn't touch the terminal, for tests.
+    #[cfg(test)]
+    pub(crate) fn headless(width: u16, height: u16, capabilities: TerminalCapabilities) -> Self {
+        Self {
+            width,
+            height,
+            buffer: Buffer::new(width, height),
+            auto_flush: true,
+            writer: Box::new(std::io::sink()),
+            color_mode: ColorMode::Auto,
+            colors_enabled: ColorMode::Auto.colors_enabled_for(&capabilities),
+            capabilities,
+        }
+    }
+
+    pub fn get_input(&self) -> Result<Event> {
+        if event::poll(std::time::Duration::from_millis(100))? {
+            let key = match event::read()? {
+                // Key releases only arrive when enabled through `KeyboardHandler`, and
+                // aren't presses
+                CrosstermEvent::Key(key) if key.kind != KeyEventKind::Release => Some(key),
+                CrosstermEvent::Resize(width, height) => return Ok(Event::Resize { width, height }),
+                _ => None,
+            };
+
+            if let Some(key) = key {
+                return Ok(Event::from_crossterm_key(&key));
+            }
+        }
+        Ok(Event::Unknown)
+    }
+
+    /// Resizes the window to match a new terminal size, e.g. after receiving
+    /// `Event::Resize`.
+    ///
+    /// The terminal and buffer are cleared, so everything needs to be redrawn.
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
+        self.width = width;
+        self.height = height;
+        self.buffer = Buffer::new(width, height);
+
         execute!(
-            stdout(),
+            self.writer,
             terminal::Clear(terminal::ClearType::All),
             cursor::MoveTo(0, 0)
         )?;
         Ok(())
     }
 
-    pub fn get_input(&self) -> Result<Event> {
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let CrosstermEvent::Key(key) = event::read()? {
-                return Ok(match key.code {
-                    KeyCode::Char(c) => Event::Character(c),
-                    KeyCode::Up => Event::KeyUp,
-                    KeyCode::Down => Event::KeyDown,
-                    KeyCode::Left => Event::KeyLeft,
-                    KeyCode::Right => Event::KeyRight,
-                    KeyCode::Delete => Event::Delete,
-                    KeyCode::Backspace => Event::Backspace,
-                    KeyCode::Enter => Event::Enter,
-                    KeyCode::F(n) => Event::FunctionKey(n),
-                    _ => Event::Unknown,
-                });
-            }
+    /// Returns the character and colors currently in the render buffer at (x, y),
+    /// or `None` if the position is out of bounds.
+    ///
+    /// Cells without colors are reported as `Transparent` on `Transparent`.
+    pub fn cell_at(&self, x: u16, y: u16) -> Option<(char, ColorPair)> {
+        self.buffer.cell(x, y).map(|cell| {
+            let colors = cell.colors.unwrap_or(ColorPair::new(Color::Transparent, Color::Transparent));
+            (cell.ch, colors)
+        })
+    }
+
+    /// Returns a copy of the buffer cell at (x, y), including its attributes.
+    pub(crate) fn buffer_cell(&self, x: u16, y: u16) -> Option<Cell> {
+        self.buffer.cell(x, y).cloned()
+    }
+
+    /// Puts a cell previously read with `buffer_cell` back, without flushing.
+    pub(crate) fn restore_cell(&mut self, x: u16, y: u16, cell: &Cell) -> Result<()> {
+        self.buffer.write_char(y, x, cell.ch, cell.colors, cell.attributes)
+    }
+
+    /// Returns the (x, y) position of every cell the next `flush` will repaint.
+    ///
+    /// This makes the renderer's diffing observable, e.g. to check that an
+    /// update only touches the cells it should. Nothing is flushed or consumed.
+    ///
+    /// With auto-flush on (the default) every write is flushed immediately, so
+    /// this is always empty. Turn it off with `set_auto_flush(false)` first.
+    pub fn pending_cells(&self) -> Vec<(u16, u16)> {
+        self.buffer.pending_cells()
+    }
+
+    /// Marks the whole window as changed, so the next `flush` repaints every
+    /// cell instead of only the ones that differ from the last frame.
+    ///
+    /// Use this when something outside the window has drawn over the terminal,
+    /// e.g. a child process or stray output on stdout.
+    pub fn invalidate(&mut self) {
+        self.buffer.invalidate();
+    }
+
+    /// Erases the terminal and resets the render buffer, the same as
+    /// `Window::clear`. Kept as an inherent method so callers don't need the
+    /// trait in scope.
+    pub fn clear(&mut self) -> Result<()> {
+        Window::clear(self)
+    }
+
+    /// Sets whether colors are emitted. Widgets keep their colors either way;
+    /// with colors disabled the renderer just doesn't output them.
+    ///
+    /// Only cells flushed after the change are affected.
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+        self.colors_enabled = mode.colors_enabled_for(&self.capabilities);
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Replaces the terminal capabilities detected when the window was
+    /// created, e.g. after `TerminalCapabilities::set_color_support` to
+    /// override a wrong guess.
+    ///
+    /// `ColorSupport::None` switches the color mode to `Never`. Otherwise the
+    /// current mode is kept, and `Auto` is resolved again against the new
+    /// capabilities (still honoring `NO_COLOR`). Every `Color` is one of the
+    /// basic ANSI colors, so there's nothing to downgrade between the other
+    /// levels of support.
+    pub fn set_capabilities(&mut self, capabilities: TerminalCapabilities) {
+        self.capabilities = capabilities;
+        if self.capabilities.color_support == ColorSupport::None {
+            self.color_mode = ColorMode::Never;
         }
-        Ok(Event::Unknown)
+        self.set_color_mode(self.color_mode);
+    }
+
+    pub fn capabilities(&self) -> &TerminalCapabilities {
+        &self.capabilities
     }
 
     pub fn set_auto_flush(&mut self, enabled: bool) {
         self.auto_flush = enabled;
     }
 
+    /// Temporarily hands the terminal back to the shell: leaves raw mode and the
+    /// alternate screen, and shows the cursor. Use this to run an external
+    /// program like `$EDITOR`.
+    ///
+    /// Mouse capture and keyboard protocol flags enabled through
+    /// `MouseHandler` and `KeyboardHandler` are turned off too, so the other
+    /// program doesn't receive their escape sequences.
+    ///
+    /// The TUI is restored when the returned guard is dropped (or when
+    /// `SuspendGuard::resume` is called, to handle errors), repainting what was
+    /// on screen. If the terminal was resized in the meantime, the window is
+    /// resized and left blank, so everything needs to be redrawn.
+    pub fn suspend(&mut self) -> Result<SuspendGuard<'_>> {
+        self.flush()?;
+
+        let mouse_capture = modes::mouse_capture_enabled();
+        let keyboard_flags = modes::keyboard_flags();
+        if mouse_capture {
+            execute!(self.writer, DisableMouseCapture)?;
+        }
+        if !keyboard_flags.is_empty() {
+            execute!(self.writer, PopKeyboardEnhancementFlags)?;
+        }
+
+        execute!(
+            self.writer,
+            style::ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        )?;
+        self.writer.flush()?;
+        disable_raw_mode()?;
+
+        Ok(SuspendGuard {
+            window: self,
+            mouse_capture,
+            keyboard_flags,
+            resumed: false,
+        })
+    }
+
+    /// Re-enters the TUI, turning back on the input modes `suspend` turned off
+    fn resume(&mut self, mouse_capture: bool, keyboard_flags: KeyboardEnhancementFlags) -> Result<()> {
+        enable_raw_mode()?;
+        execute!(
+            self.writer,
+            terminal::EnterAlternateScreen,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::Hide,
+            cursor::MoveTo(0, 0)
+        )?;
+        if mouse_capture {
+            execute!(self.writer, EnableMouseCapture)?;
+        }
+        if !keyboard_flags.is_empty() {
+            execute!(self.writer, PushKeyboardEnhancementFlags(keyboard_flags))?;
+        }
+
+        // The terminal may have been resized while suspended
+        let (cols, rows) = terminal::size()?;
+        if (cols, rows) != (self.width, self.height) {
+            self.width = cols;
+            self.height = rows;
+            self.buffer = Buffer::new(cols, rows);
+            return Ok(());
+        }
+
+        // Repaint everything, since the diff thinks the screen is unchanged
+        self.buffer.invalidate();
+        self.flush()
+    }
+
+    /// Suspends the TUI, runs `command` with the terminal's stdin/stdout/stderr,
+    /// waits for it to exit, then restores and repaints the TUI.
+    ///
+    /// Handy for "open in `$EDITOR`" style features. The TUI is restored even
+    /// if the command fails to start.
+    pub fn run_external(&mut self, command: &mut Command) -> Result<ExitStatus> {
+        let guard = self.suspend()?;
+        let status = command
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status();
+        guard.resume()?;
+        Ok(status?)
+    }
+
     pub fn flush(&mut self) -> Result<()> {
         let changes = self.buffer.process_changes();
         for change in changes {
-            if let Some(colors) = change.colors {
+            if !change.attributes.is_empty() {
+                set_attributes(&mut self.writer, change.attributes)?;
+            }
+
+            if let Some(colors) = change.colors.filter(|_| self.colors_enabled) {
                 execute!(
-                    stdout(),
+                    self.writer,
                     cursor::MoveTo(change.x, change.y),
                     style::SetForegroundColor(colors.fg.to_crossterm()),
                     style::SetBackgroundColor(colors.bg.to_crossterm()),
@@ -99,13 +379,17 @@ impl TerminalWindow {
                 )?;
             } else {
                 execute!(
-                    stdout(),
+                    self.writer,
                     cursor::MoveTo(change.x, change.y),
                     style::Print(&change.text)
                 )?;
             }
+
+            if !change.attributes.is_empty() {
+                execute!(self.writer, style::SetAttribute(Attribute::Reset))?;
+            }
         }
-        stdout().flush()?;
+        self.writer.flush()?;
         Ok(())
     }
 }
@@ -137,10 +421,44 @@ impl Window for TerminalWindow {
         Ok(())
     }
 
+    fn write_str_styled(&mut self, y: u16, x: u16, s: &str, style: &Style) -> Result<()> {
+        if y >= self.height || x >= self.width {
+            return Err(Error::WindowError("Position out of bounds. Consider resizing your window".into()));
+        }
+
+        self.buffer.write_str_styled(y, x, s, style.colors, style.attributes)?;
+
+        if self.auto_flush {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes straight into the buffer, clipping the run at the right edge.
+    fn write_repeated(&mut self, y: u16, x: u16, ch: char, count: u16, colors: Option<ColorPair>) -> Result<()> {
+        if y >= self.height || x >= self.width {
+            return Err(Error::WindowError("Position out of bounds. Consider resizing your window".into()));
+        }
+
+        let end = x.saturating_add(count).min(self.width);
+        for col in x..end {
+            self.buffer.write_char(y, col, ch, colors, TextAttributes::none())?;
+        }
+
+        if self.auto_flush {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
     fn get_size(&self) -> (u16, u16) {
         (self.width, self.height)
     }
 
+    fn style_at(&self, x: u16, y: u16) -> Option<ColorPair> {
+        self.cell_at(x, y).map(|(_, colors)| colors)
+    }
+
     fn clear_screen(&mut self) -> Result<()> {
         self.buffer.clear();
 
@@ -150,6 +468,19 @@ impl Window for TerminalWindow {
         Ok(())
     }
 
+    fn clear(&mut self) -> Result<()> {
+        execute!(
+            self.writer,
+            style::ResetColor,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+
+        // The terminal is blank now, so the buffer has nothing left to diff against
+        self.buffer.reset();
+        Ok(())
+    }
+
     fn clear_line(&mut self, y: u16) -> Result<()> {
         if y >= self.height {
             return Err(Error::WindowError("Line number out of bounds".into()));
@@ -185,11 +516,53 @@ impl Window for TerminalWindow {
     }
 }
 
+/// Restores the TUI suspended by `TerminalWindow::suspend` when dropped.
+pub struct SuspendGuard<'a> {
+    window: &'a mut TerminalWindow,
+    mouse_capture: bool,
+    keyboard_flags: KeyboardEnhancementFlags,
+    resumed: bool,
+}
+
+impl SuspendGuard<'_> {
+    /// Restores the TUI now, returning any error instead of ignoring it like
+    /// dropping the guard does.
+    pub fn resume(mut self) -> Result<()> {
+        self.resumed = true;
+        self.window.resume(self.mouse_capture, self.keyboard_flags)
+    }
+}
+
+impl Drop for SuspendGuard<'_> {
+    fn drop(&mut self) {
+        if !self.resumed {
+            let _ = self.window.resume(self.mouse_capture, self.keyboard_flags);
+        }
+    }
+}
+
+fn set_attributes(writer: &mut impl Write, attributes: TextAttributes) -> Result<()> {
+    let flags = [
+        (attributes.bold, Attribute::Bold),
+        (attributes.italic, Attribute::Italic),
+        (attributes.underline, Attribute::Underlined),
+        (attributes.reverse, Attribute::Reverse),
+        (attributes.blink, Attribute::SlowBlink),
+    ];
+
+    for (enabled, attribute) in flags {
+        if enabled {
+            execute!(writer, style::SetAttribute(attribute))?;
+        }
+    }
+    Ok(())
+}
+
 impl Drop for TerminalWindow {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
         let _ = execute!(
-            stdout(),
+            self.writer,
             style::ResetColor,
             terminal::Clear(terminal::ClearType::All),
             cursor::MoveTo(0, 0),
@@ -198,4 +571,46 @@ impl Drop for TerminalWindow {
         );
         let _ = self.flush();
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(color_support: ColorSupport) -> TerminalCapabilities {
+        TerminalCapabilities {
+            color_support,
+            is_tty: true,
+            term: None,
+            colorterm: None,
+            mouse: true,
+        }
+    }
+
+    #[test]
+    fn set_capabilities_keeps_the_color_mode() {
+        let mut window = TerminalWindow::headless(10, 2, capabilities(ColorSupport::Basic));
+        window.set_color_mode(ColorMode::Never);
+        window.set_capabilities(capabilities(ColorSupport::TrueColor));
+        assert_eq!(window.color_mode(), ColorMode::Never);
+        assert!(!window.colors_enabled);
+
+        window.set_color_mode(ColorMode::Always);
+        window.set_capabilities(capabilities(ColorSupport::None));
+        assert_eq!(window.color_mode(), ColorMode::Never);
+        assert!(!window.colors_enabled);
+    }
+
+    #[test]
+    fn no_color_survives_set_capabilities() {
+        std::env::remove_var("CLICOLOR_FORCE");
+        std::env::set_var("NO_COLOR", "1");
+        let mut window = TerminalWindow::headless(10, 2, capabilities(ColorSupport::Basic));
+        window.set_capabilities(capabilities(ColorSupport::TrueColor));
+        let enabled = window.colors_enabled;
+        std::env::remove_var("NO_COLOR");
+
+        assert_eq!(window.color_mode(), ColorMode::Auto);
+        assert!(!enabled);
+    }
+}